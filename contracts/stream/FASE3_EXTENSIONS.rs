@@ -447,9 +447,9 @@ impl StreamContract {
 //    - Useful for event delays or extensions
 //
 // 4. **HYBRID Model**:
-//    - Create upfront stream (30%) when assignment starts
-//    - After review approval, create remaining stream (70% * multiplier)
-//    - Use two separate stream_ids for same assignment
+//    - Merged into lib.rs as create_hybrid_stream (upfront_bps = 3000)
+//    - After review approval, call record_review with the multiplier in basis points
+//    - One stream_id per assignment; the unearned remainder is refunded on-chain
 //
 // 5. **Security Considerations**:
 //    - Only sender (employer) can pause/resume/top-up/extend
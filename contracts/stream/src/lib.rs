@@ -4,16 +4,38 @@
 //! 
 //! ## Features
 //! - **Stopwatch Vesting**: Builder starts/stops work to accumulate earnings
+//! - **Linear Vesting**: Salary-style streams that vest between a start and stop time
 //! - **Escrow**: Full budget locked upfront
 //! - **Flexible Withdrawals**: Builder can withdraw earned amounts anytime
 //! - **Cancellable**: Admin can cancel, refunding unearned budget
+//! - **Pausable**: Admin can put a stream on hold and resume it later
+//! - **Top-ups**: Admin can add budget to a live stream without recreating it
+//! - **Budget Cuts**: Admin can reclaim unearned budget while the stream stays alive
+//! - **Cliffs**: Optional probation period before accrued earnings become withdrawable
+//! - **Session Limits**: Optional max session length and heartbeats auto-stop forgotten clocks
+//! - **Accrual Caps**: Optional daily/weekly limits in seconds or tokens (UTC periods)
+//! - **Overtime**: Optional higher rate beyond a weekly hours threshold
+//! - **Milestones**: Fixed-scope payouts released by sender approval of submitted work
+//! - **Hybrid**: Upfront tranche plus a remainder scaled by the sender's review multiplier
+//! - **One-Time Payments**: Escrowed lump sums with optional release time and approval
+//! - **Split Streams**: One budget and clock shared among recipients by fixed basis-point shares
+//! - **Team Pools**: One budget drawn down by several builders, each on their own clock and rate
+//! - **Batch Creation**: A whole payroll of stopwatch streams funded by a single transfer
+//! - **Batch Withdrawals**: Sweep a builder's streams with one transfer per token
+//! - **Recipient Transfers**: Builders hand a stream to a new wallet (propose, then accept)
+//! - **Reassignment**: Admin moves a stream to a new builder after paying out the old one
+//! - **Operators**: Builders delegate clock control (never withdrawals) to services like time trackers
+//! - **Signed Clock Actions**: Start/stop signed with a session key and submitted by any relayer
+//! - **Relayer Fees**: Signed actions and withdrawals can pay their relayer a capped fee from earnings
+//! - **Session Log**: Every stopwatch session is stored with its credit and an optional work-proof hash
+//! - **Timesheet Review**: Optional window for the admin to approve sessions before they are withdrawable
 //! 
 //! ## Security Model
 //! - **Authorization**: Strict checks on sender/recipient actions
 //! - **Math**: Checked arithmetic for all monetary calculations
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, contracterror, Address, Env, Symbol};
+use soroban_sdk::{contract, contractimpl, contracttype, contracterror, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Vec};
 
 mod test;
 
@@ -40,6 +62,55 @@ pub enum StreamError {
     StreamAlreadyRunning = 14,
     StreamNotRunning = 15,
     BudgetExceeded = 16,
+    UnsupportedAccrualMode = 17,
+    InvalidStateTransition = 18,
+    StreamOnHold = 19,
+    StreamNotOnHold = 20,
+    RefundExceedsUnearned = 21,
+    CliffNotReached = 22,
+    InvalidMilestone = 23,
+    MilestoneNotSubmitted = 24,
+    MilestoneAlreadySubmitted = 25,
+    ReviewAlreadyRecorded = 26,
+    AwaitingApproval = 27,
+    ApprovalNotRequired = 28,
+    InvalidSplit = 29,
+    PoolNotFound = 30,
+    NotPoolMember = 31,
+    InvalidPoolMembers = 32,
+    NoPendingTransfer = 33,
+    UnauthorizedOperator = 34,
+    NoSessionKey = 35,
+    InvalidNonce = 36,
+    StaleClockPayload = 37,
+    FeeExceedsMax = 38,
+    InvalidSession = 39,
+}
+
+/// Maximum age of a linear stream's start time at creation (30 days)
+const MAX_START_TIME_AGE: u64 = 30 * 24 * 60 * 60;
+
+/// Basis points in 100%
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Length of a UTC day, the unit accrual caps are tracked in
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// How far a signed clock payload's timestamp may be from the ledger time (5 minutes)
+const MAX_CLOCK_DRIFT: u64 = 5 * 60;
+
+/// 1970-01-01 was a Thursday; shifting by 3 days makes weeks run Monday to Sunday
+const WEEK_START_OFFSET_DAYS: u64 = 3;
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AccrualMode {
+    Stopwatch = 0,  // Accrues only while the builder's clock is running
+    Linear = 1,     // Vests continuously between start_time and stop_time
+    Milestone = 2,  // Releases fixed amounts as the sender approves submitted milestones
+    Hybrid = 3,     // Upfront tranche, remainder scaled by a review multiplier
+    OneTime = 4,    // Whole deposit released at start_time (and sender approval, if required)
 }
 
 #[contracttype]
@@ -53,257 +124,2770 @@ pub enum StreamStatus {
     Cancelled = 4,  // Cancelled by admin
 }
 
-#[contracttype]
-#[derive(Clone, Debug, PartialEq)]
-pub struct Stream {
-    pub sender: Address,
-    pub recipient: Address,
-    pub deposit: i128,          // Total initial budget
-    pub rate_per_second: i128,  // Earnings per second when running
-    pub remaining_balance: i128,// Funds currently in contract (Deposit - Withdrawn)
-    pub total_accrued: i128,    // Total earnings finalized (excluding current session if running)
-    pub last_update_time: u64,  // Last time start/stop/withdraw/cancel happened
-    pub is_running: bool,       // Is the clock currently ticking?
-    pub is_cancelled: bool,     // Has the stream been cancelled?
-    pub token_address: Address,
-}
+/// How a stream accrues, chosen at creation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Schedule {
+    Stopwatch(i128),    // rate_per_second while the clock is running
+    Linear(u64, u64),   // (start_time, stop_time)
+}
+
+/// A milestone as defined by the sender at creation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneSpec {
+    pub amount: i128,
+    pub description_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MilestoneStatus {
+    Pending = 0,    // Not submitted yet
+    Submitted = 1,  // Waiting for the sender's review
+    Approved = 2,   // Amount released to the builder
+    Rejected = 3,   // Sent back; the builder can resubmit
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub amount: i128,
+    pub description_hash: BytesN<32>,
+    pub proof_hash: BytesN<32>,     // Latest proof submitted by the builder (zeroed until then)
+    pub status: MilestoneStatus,
+}
+
+/// A recipient's fixed share of a split stream, as defined by the sender at creation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitSpec {
+    pub recipient: Address,
+    pub share_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Split {
+    pub recipient: Address,
+    pub share_bps: u32,
+    pub withdrawn: i128,    // Paid out to this recipient so far
+}
+
+/// A team pool member as defined by the sender at creation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolMemberSpec {
+    pub recipient: Address,
+    pub rate_per_second: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolMember {
+    pub recipient: Address,
+    pub rate_per_second: i128,
+    pub running: bool,      // Member's own clock is ticking
+    pub accrued: i128,      // Earned so far (up to the pool's last_update_time)
+    pub withdrawn: i128,    // Paid out so far
+}
+
+/// Shared escrow for a team; members are stored under `DataKey::PoolMembers`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pool {
+    pub sender: Address,
+    pub deposit: i128,          // Total budget shared by all members
+    pub remaining_balance: i128,// Funds currently in contract
+    pub total_accrued: i128,    // Sum of every member's accrual
+    pub last_update_time: u64,  // Running clocks have accrued up to here
+    pub status: StreamStatus,   // Pending until the first clock starts; Completed once the budget is used up
+    pub token_address: Address,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CliffCancelPolicy {
+    Forfeit = 0,    // Cancelling before the cliff pays the builder nothing
+    ProRata = 1,    // Cancelling before the cliff pays everything accrued so far
+}
+
+/// Withdrawals are locked until every configured condition is met; the default is no cliff
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cliff {
+    pub time: u64,                      // Earliest withdrawal timestamp (0 = no time cliff)
+    pub amount: i128,                   // Minimum accrued before withdrawing (0 = no amount cliff)
+    pub on_cancel: CliffCancelPolicy,   // What the builder gets if cancelled before the cliff
+}
+
+impl Default for Cliff {
+    fn default() -> Self {
+        Cliff {
+            time: 0,
+            amount: 0,
+            on_cancel: CliffCancelPolicy::ProRata,
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CapUnit {
+    Seconds = 0,    // Caps limit credited working time
+    Tokens = 1,     // Caps limit earned amount
+}
+
+/// Per-period accrual limits; 0 means no cap for that period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccrualCaps {
+    pub daily: i128,
+    pub weekly: i128,
+    pub unit: CapUnit,
+}
+
+impl Default for AccrualCaps {
+    fn default() -> Self {
+        AccrualCaps {
+            daily: 0,
+            weekly: 0,
+            unit: CapUnit::Seconds,
+        }
+    }
+}
+
+/// What has been credited in one UTC day or week
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PeriodUsage {
+    pub index: u64,     // Days (or weeks) since the epoch
+    pub seconds: u64,   // Seconds credited in this period
+    pub amount: i128,   // Tokens credited in this period
+}
+
+/// Higher rate paid once a week's credited time passes a threshold; a zero rate disables it
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OvertimeTier {
+    pub threshold_seconds: u64, // Credited seconds per UTC week paid at the base rate
+    pub rate_per_second: i128,  // Rate for every credited second beyond the threshold
+}
+
+/// Earnings split by rate tier
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TierTotals {
+    pub regular: i128,  // Earned at the base rate_per_second
+    pub overtime: i128, // Earned at the overtime rate
+}
+
+/// Optional per-stream settings; `StreamOptions::default()` gives a plain stream
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StreamOptions {
+    pub cliff: Cliff,
+    pub max_session_seconds: u64,   // Longest a single session can accrue (0 = unlimited, Stopwatch only)
+    pub heartbeat_grace: u64,       // Accrual stops this long after the last heartbeat (0 = off, Stopwatch only)
+    pub caps: AccrualCaps,          // Daily/weekly limits (Stopwatch only)
+    pub overtime: OvertimeTier,     // Weekly overtime rate (Stopwatch only)
+    pub review_window: u64,         // Closed sessions wait this long for approval before auto-approving (0 = off, Stopwatch only)
+}
+
+/// One stopwatch stream in a `create_streams_batch` payroll manifest
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchStreamEntry {
+    pub recipient: Address,
+    pub deposit: i128,
+    pub rate_per_second: i128,
+    pub options: StreamOptions,
+}
+
+/// A recipient handover waiting for the new address to accept
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecipientTransfer {
+    pub new_recipient: Address,
+    pub settle_accrued: bool,   // Pay the current recipient what is withdrawable before handing over
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ClockAction {
+    Start = 0,
+    Stop = 1,
+    Heartbeat = 2,
+}
+
+/// A clock action signed off-chain by the recipient's session key
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClockPayload {
+    pub stream_id: u64,
+    pub action: ClockAction,
+    pub timestamp: u64, // When it was signed; must be within MAX_CLOCK_DRIFT of the ledger time
+    pub nonce: u64,     // Must equal the stream's next signed-payload nonce
+    pub relayer: Address,   // Who may be paid for submitting it
+    pub max_fee: i128,      // Most the relayer may take from the recipient's earnings (0 = no fee)
+}
+
+/// A withdrawal signed off-chain by the recipient's session key
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawPayload {
+    pub stream_id: u64,
+    pub amount: i128,           // Paid to `destination`, on top of any relayer fee
+    pub destination: Address,
+    pub timestamp: u64,
+    pub nonce: u64,
+    pub relayer: Address,
+    pub max_fee: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionKey {
+    pub recipient: Address, // Recipient who registered the key
+    pub public_key: BytesN<32>,
+}
+
+/// A closed stopwatch session, as shown on a timesheet
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WorkSession {
+    pub start: u64,
+    pub stop: u64,                  // When accrual stopped (the cutoff for expired sessions)
+    pub seconds: u64,               // Seconds paid for after caps and the budget
+    pub amount: i128,               // Amount credited
+    pub proof_hash: BytesN<32>,     // Work proof passed to `stop_work_with_proof` (zeroed if none)
+    pub approved: bool,             // Counts toward the withdrawable amount
+    pub rejected: bool,             // Disputed by the sender and refunded to them
+}
+
+/// What `withdraw_many` paid out of one stream
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalReceipt {
+    pub stream_id: u64,
+    pub token_address: Address,
+    pub amount: i128,   // 0 when nothing was withdrawable
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stream {
+    pub sender: Address,
+    pub recipient: Address,
+    pub deposit: i128,          // Total initial budget
+    pub mode: AccrualMode,      // How earnings accrue (see `AccrualMode`)
+    pub rate_per_second: i128,  // Earnings per second when running (Stopwatch only)
+    pub start_time: u64,        // Vesting start (Linear only)
+    pub stop_time: u64,         // Vesting end (Linear only)
+    pub remaining_balance: i128,// Funds currently in contract (Deposit - Withdrawn)
+    pub total_accrued: i128,    // Total earnings finalized (excluding current session if Active)
+    pub last_update_time: u64,  // Accrual is up to date until here
+    pub status: StreamStatus,   // Lifecycle state, only changed through `transition`
+    pub paused_at: Option<u64>, // Set while the sender has the stream on hold
+    pub paused_duration: u64,   // Total time spent on hold
+    pub cliff: Cliff,           // Withdrawal lock (see `Cliff`)
+    pub max_session_seconds: u64,   // Session length limit (0 = unlimited)
+    pub heartbeat_grace: u64,       // Heartbeat window (0 = heartbeats not required)
+    pub session_start: u64,         // When the current/last session started
+    pub last_heartbeat: u64,        // Last proof of life for the current session
+    pub session_seconds: u64,       // Seconds credited in the current session
+    pub session_amount: i128,       // Amount credited in the current session
+    pub session_count: u32,         // Closed sessions stored under `DataKey::Session`
+    pub review_window: u64,         // Timesheet review window (0 = sessions are approved on close)
+    pub first_pending_session: u32, // Sessions from this index on are awaiting review
+    pub pending_amount: i128,       // Credited in sessions awaiting review
+    pub caps: AccrualCaps,          // Daily/weekly accrual limits
+    pub day_usage: PeriodUsage,     // Credited in the current UTC day
+    pub week_usage: PeriodUsage,    // Credited in the current UTC week
+    pub overtime: OvertimeTier,     // Weekly overtime rate
+    pub tier_totals: TierTotals,    // Finalized earnings per rate tier (Stopwatch only)
+    pub upfront_bps: u32,           // Share of the deposit released at creation (Hybrid only)
+    pub review_multiplier_bps: Option<u32>, // Recorded review outcome (Hybrid only)
+    pub awaiting_approval: bool,    // Release still needs the sender's approval (OneTime only)
+    pub split: bool,                // Earnings are shared among the recipients in `DataKey::Splits`
+    pub token_address: Address,
+}
+
+#[contracttype]
+pub enum DataKey {
+    Stream(u64),
+    Milestones(u64),
+    Splits(u64),
+    RecipientStreams(Address),
+    PendingTransfer(u64),
+    Operator(Address, Address),         // (recipient, operator) for all of the recipient's streams
+    StreamOperator(u64, Address),       // (stream_id, operator) -> recipient who granted it
+    SessionKey(u64),
+    ClockNonce(u64),
+    Session(u64, u32),                  // (stream_id, index) -> WorkSession
+    NextStreamId,
+    Pool(u64),
+    PoolMembers(u64),
+    NextPoolId,
+}
+
+#[contractimpl]
+impl StreamContract {
+    /// Creates a new job/stream with a fixed budget and rate
+    pub fn create_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit: i128,
+        token_address: Address,
+        rate_per_second: i128,
+    ) -> Result<u64, StreamError> {
+        Self::create_stream_with_options(
+            env,
+            sender,
+            recipient,
+            deposit,
+            token_address,
+            Schedule::Stopwatch(rate_per_second),
+            StreamOptions::default(),
+        )
+    }
+
+    /// Creates a salary-style stream that vests linearly between start_time and stop_time
+    pub fn create_linear_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit: i128,
+        token_address: Address,
+        start_time: u64,
+        stop_time: u64,
+    ) -> Result<u64, StreamError> {
+        Self::create_stream_with_options(
+            env,
+            sender,
+            recipient,
+            deposit,
+            token_address,
+            Schedule::Linear(start_time, stop_time),
+            StreamOptions::default(),
+        )
+    }
+
+    /// Creates a stream with any schedule plus optional settings such as a cliff
+    pub fn create_stream_with_options(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit: i128,
+        token_address: Address,
+        schedule: Schedule,
+        options: StreamOptions,
+    ) -> Result<u64, StreamError> {
+        sender.require_auth();
+
+        let stream = Self::new_stream(&env, sender, recipient, deposit, token_address, schedule, options)?;
+
+        Ok(Self::open_stream(&env, stream))
+    }
+
+    /// Creates a stopwatch stream per manifest entry, escrowing the summed deposit in one transfer;
+    /// any invalid entry fails the whole batch
+    pub fn create_streams_batch(
+        env: Env,
+        sender: Address,
+        token_address: Address,
+        entries: Vec<BatchStreamEntry>,
+    ) -> Result<Vec<u64>, StreamError> {
+        sender.require_auth();
+
+        let mut streams: Vec<Stream> = Vec::new(&env);
+        let mut total_deposit: i128 = 0;
+        for entry in entries.iter() {
+            let stream = Self::new_stream(
+                &env,
+                sender.clone(),
+                entry.recipient,
+                entry.deposit,
+                token_address.clone(),
+                Schedule::Stopwatch(entry.rate_per_second),
+                entry.options,
+            )?;
+            total_deposit = total_deposit.checked_add(stream.deposit).ok_or(StreamError::Overflow)?;
+            streams.push_back(stream);
+        }
+
+        if total_deposit > 0 {
+            let token = soroban_sdk::token::Client::new(&env, &token_address);
+            token.transfer(&sender, &env.current_contract_address(), &total_deposit);
+        }
+
+        let mut stream_ids = Vec::new(&env);
+        for stream in streams.iter() {
+            stream_ids.push_back(Self::register_stream(&env, stream));
+        }
+
+        Ok(stream_ids)
+    }
+
+    /// Creates a fixed-scope stream paid out milestone by milestone; the deposit is the sum of all milestones
+    pub fn create_milestone_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token_address: Address,
+        milestones: Vec<MilestoneSpec>,
+    ) -> Result<u64, StreamError> {
+        sender.require_auth();
+
+        if milestones.is_empty() {
+            return Err(StreamError::InvalidMilestone);
+        }
+        if sender == recipient {
+            return Err(StreamError::SenderCannotBeRecipient);
+        }
+
+        let mut deposit: i128 = 0;
+        let mut stored = Vec::new(&env);
+        for spec in milestones.iter() {
+            if spec.amount <= 0 {
+                return Err(StreamError::InvalidAmount);
+            }
+            deposit = deposit.checked_add(spec.amount).ok_or(StreamError::Overflow)?;
+            stored.push_back(Milestone {
+                amount: spec.amount,
+                description_hash: spec.description_hash,
+                proof_hash: BytesN::from_array(&env, &[0; 32]),
+                status: MilestoneStatus::Pending,
+            });
+        }
+
+        let stream = Self::base_stream(&env, sender, recipient, deposit, token_address, AccrualMode::Milestone);
+        let stream_id = Self::open_stream(&env, stream);
+        env.storage().persistent().set(&DataKey::Milestones(stream_id), &stored);
+
+        Ok(stream_id)
+    }
+
+    /// Creates a HYBRID stream: `upfront_bps` of the deposit is withdrawable immediately,
+    /// the rest waits for the sender's review multiplier
+    pub fn create_hybrid_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        deposit: i128,
+        token_address: Address,
+        upfront_bps: u32,
+    ) -> Result<u64, StreamError> {
+        sender.require_auth();
+
+        if deposit <= 0 || upfront_bps as i128 > BPS_DENOMINATOR {
+            return Err(StreamError::InvalidAmount);
+        }
+        if sender == recipient {
+            return Err(StreamError::SenderCannotBeRecipient);
+        }
+
+        let mut stream = Self::base_stream(&env, sender, recipient, deposit, token_address, AccrualMode::Hybrid);
+        stream.upfront_bps = upfront_bps;
+        stream.total_accrued = deposit.checked_mul(upfront_bps as i128).ok_or(StreamError::Overflow)? / BPS_DENOMINATOR;
+
+        Ok(Self::open_stream(&env, stream))
+    }
+
+    /// Creates a stream whose earnings are divided among several recipients by fixed shares
+    /// summing to 100%; the first recipient runs the clock for stopwatch schedules
+    pub fn create_split_stream(
+        env: Env,
+        sender: Address,
+        recipients: Vec<SplitSpec>,
+        deposit: i128,
+        token_address: Address,
+        schedule: Schedule,
+    ) -> Result<u64, StreamError> {
+        sender.require_auth();
+
+        let lead = recipients.get(0).ok_or(StreamError::InvalidSplit)?.recipient;
+
+        let mut total_bps: i128 = 0;
+        let mut stored: Vec<Split> = Vec::new(&env);
+        for spec in recipients.iter() {
+            if spec.share_bps == 0 || stored.iter().any(|s| s.recipient == spec.recipient) {
+                return Err(StreamError::InvalidSplit);
+            }
+            if spec.recipient == sender {
+                return Err(StreamError::SenderCannotBeRecipient);
+            }
+            total_bps += spec.share_bps as i128;
+            stored.push_back(Split {
+                recipient: spec.recipient,
+                share_bps: spec.share_bps,
+                withdrawn: 0,
+            });
+        }
+        if total_bps != BPS_DENOMINATOR {
+            return Err(StreamError::InvalidSplit);
+        }
+
+        let mut stream = Self::new_stream(&env, sender, lead, deposit, token_address, schedule, StreamOptions::default())?;
+        stream.split = true;
+
+        let stream_id = Self::open_stream(&env, stream);
+        env.storage().persistent().set(&DataKey::Splits(stream_id), &stored);
+        for split in stored.iter().skip(1) {
+            Self::index_recipient(&env, &split.recipient, stream_id);
+        }
+
+        Ok(stream_id)
+    }
+
+    /// Locks a one-time payment for the recipient, released at `release_time` (0 = immediately)
+    /// and, if `requires_approval` is set, once the sender approves it
+    pub fn pay_once(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        amount: i128,
+        token_address: Address,
+        release_time: u64,
+        requires_approval: bool,
+    ) -> Result<u64, StreamError> {
+        sender.require_auth();
+
+        if amount <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+        if sender == recipient {
+            return Err(StreamError::SenderCannotBeRecipient);
+        }
+
+        let mut stream = Self::base_stream(&env, sender, recipient, amount, token_address, AccrualMode::OneTime);
+        stream.start_time = release_time.max(env.ledger().timestamp());
+        stream.awaiting_approval = requires_approval;
+
+        Ok(Self::open_stream(&env, stream))
+    }
+
+    /// Builder starts working (clock starts ticking)
+    pub fn start_work(env: Env, stream_id: u64) -> Result<(), StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+        Self::start_work_as(env, stream_id, stream.recipient)
+    }
+
+    /// Starts the clock as `caller`: the recipient or one of their operators
+    pub fn start_work_as(env: Env, stream_id: u64, caller: Address) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        Self::authorize_clock(&env, stream_id, &stream, &caller)?;
+        Self::begin_session(&env, stream_id, &mut stream, caller)?;
+
+        env.storage().persistent().set(&key, &stream);
+
+        Ok(())
+    }
+
+    /// Builder stops working (clock stops, earnings accrued)
+    pub fn stop_work(env: Env, stream_id: u64) -> Result<(), StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+        Self::stop_work_as(env, stream_id, stream.recipient)
+    }
+
+    /// Stops the clock as `caller`: the recipient or one of their operators
+    pub fn stop_work_as(env: Env, stream_id: u64, caller: Address) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        Self::authorize_clock(&env, stream_id, &stream, &caller)?;
+        Self::end_session(&env, stream_id, &mut stream, caller)?;
+
+        env.storage().persistent().set(&key, &stream);
+
+        Ok(())
+    }
+
+    /// Stops the clock as `caller` and attaches a work-proof hash (e.g. a commit hash) to the logged session
+    pub fn stop_work_with_proof(env: Env, stream_id: u64, caller: Address, proof_hash: BytesN<32>) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        Self::authorize_clock(&env, stream_id, &stream, &caller)?;
+        Self::end_session(&env, stream_id, &mut stream, caller)?;
+
+        let session_key = DataKey::Session(stream_id, stream.session_count - 1);
+        let mut session: WorkSession = env.storage().persistent().get(&session_key).ok_or(StreamError::StreamNotRunning)?;
+        session.proof_hash = proof_hash;
+        env.storage().persistent().set(&session_key, &session);
+
+        env.storage().persistent().set(&key, &stream);
+
+        Ok(())
+    }
+
+    /// Builder proves they are still working, extending accrual by another grace window
+    pub fn heartbeat(env: Env, stream_id: u64) -> Result<(), StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+        Self::heartbeat_as(env, stream_id, stream.recipient)
+    }
+
+    /// Sends a heartbeat as `caller`: the recipient or one of their operators
+    pub fn heartbeat_as(env: Env, stream_id: u64, caller: Address) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        Self::authorize_clock(&env, stream_id, &stream, &caller)?;
+        Self::extend_session(&env, stream_id, &mut stream, caller)?;
+
+        env.storage().persistent().set(&key, &stream);
+
+        Ok(())
+    }
+
+    /// Recipient registers the ed25519 key their app signs clock payloads with (replacing any previous one)
+    pub fn set_session_key(env: Env, stream_id: u64, public_key: BytesN<32>) -> Result<(), StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+        stream.recipient.require_auth();
+
+        let session_key = SessionKey { recipient: stream.recipient.clone(), public_key: public_key.clone() };
+        env.storage().persistent().set(&DataKey::SessionKey(stream_id), &session_key);
+
+        env.events().publish(
+            (Symbol::new(&env, "session_key_set"), stream.recipient, stream_id),
+            public_key,
+        );
+
+        Ok(())
+    }
+
+    /// Recipient removes the stream's session key; signed payloads are rejected until a new one is set
+    pub fn revoke_session_key(env: Env, stream_id: u64) -> Result<(), StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+        stream.recipient.require_auth();
+
+        env.storage().persistent().remove(&DataKey::SessionKey(stream_id));
+
+        env.events().publish(
+            (Symbol::new(&env, "session_key_revoked"), stream.recipient, stream_id),
+            (),
+        );
+
+        Ok(())
+    }
+
+    /// Applies a clock action signed with the recipient's session key; anyone may relay it.
+    /// The signature covers `(contract address, payload)` as XDR, and an invalid one aborts the call.
+    /// `fee` (at most the signed `max_fee`) is paid to the signed relayer out of the recipient's earnings.
+    pub fn submit_clock_action(env: Env, payload: ClockPayload, signature: BytesN<64>, fee: i128) -> Result<(), StreamError> {
+        let stream_id = payload.stream_id;
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        let message = (env.current_contract_address(), payload.clone()).to_xdr(&env);
+        Self::verify_signed_payload(&env, stream_id, &stream, payload.timestamp, payload.nonce, message, signature)?;
+
+        let recipient = stream.recipient.clone();
+        match payload.action {
+            ClockAction::Start => Self::begin_session(&env, stream_id, &mut stream, recipient)?,
+            ClockAction::Stop => Self::end_session(&env, stream_id, &mut stream, recipient)?,
+            ClockAction::Heartbeat => Self::extend_session(&env, stream_id, &mut stream, recipient)?,
+        }
+
+        // Charged after the action so a stop can pay its fee from the session it just closed
+        Self::pay_relayer_fee(&env, stream_id, &mut stream, &payload.relayer, fee, payload.max_fee)?;
+
+        env.storage().persistent().set(&key, &stream);
+
+        Ok(())
+    }
+
+    /// Pays out a withdrawal signed with the recipient's session key; anyone may relay it.
+    /// `fee` (at most the signed `max_fee`) comes out of the same available balance as `amount`.
+    pub fn submit_withdrawal(env: Env, payload: WithdrawPayload, signature: BytesN<64>, fee: i128) -> Result<(), StreamError> {
+        let stream_id = payload.stream_id;
+        let mut stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+        let message = (env.current_contract_address(), payload.clone()).to_xdr(&env);
+        Self::verify_signed_payload(&env, stream_id, &stream, payload.timestamp, payload.nonce, message, signature)?;
+
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+        if payload.amount <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+
+        let recipient = stream.recipient.clone();
+        let available = Self::available_to(&env, stream_id, &mut stream, &recipient)?;
+        if available <= 0 {
+            return Err(StreamError::NothingToWithdraw);
+        }
+        if payload.amount.checked_add(fee).ok_or(StreamError::Overflow)? > available {
+            return Err(StreamError::WithdrawExceedsAvailable);
+        }
+
+        let token = soroban_sdk::token::Client::new(&env, &stream.token_address);
+        token.transfer(&env.current_contract_address(), &payload.destination, &payload.amount);
+        Self::record_withdrawal(&env, stream_id, &mut stream, &recipient, payload.amount)?;
+
+        if payload.destination != recipient {
+            env.events().publish(
+                (Symbol::new(&env, "withdrawn_to"), recipient, stream_id),
+                (payload.destination, payload.amount),
+            );
+        }
+
+        Self::pay_relayer_fee(&env, stream_id, &mut stream, &payload.relayer, fee, payload.max_fee)
+    }
+
+    /// Checks a signed payload against the stream's session key, freshness bound and nonce,
+    /// then consumes the nonce
+    fn verify_signed_payload(
+        env: &Env,
+        stream_id: u64,
+        stream: &Stream,
+        timestamp: u64,
+        nonce: u64,
+        message: Bytes,
+        signature: BytesN<64>,
+    ) -> Result<(), StreamError> {
+        // Session keys only speak for the recipient who registered them
+        let session_key: SessionKey = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SessionKey(stream_id))
+            .filter(|k: &SessionKey| k.recipient == stream.recipient)
+            .ok_or(StreamError::NoSessionKey)?;
+
+        if timestamp.abs_diff(env.ledger().timestamp()) > MAX_CLOCK_DRIFT {
+            return Err(StreamError::StaleClockPayload);
+        }
+
+        let nonce_key = DataKey::ClockNonce(stream_id);
+        let expected: u64 = env.storage().persistent().get(&nonce_key).unwrap_or(0);
+        if nonce != expected {
+            return Err(StreamError::InvalidNonce);
+        }
+
+        env.crypto().ed25519_verify(&session_key.public_key, &message, &signature);
+
+        env.storage().persistent().set(&nonce_key, &(expected + 1));
+        Ok(())
+    }
+
+    /// Pays the relayer `fee` out of the recipient's withdrawable earnings, never more than
+    /// the recipient signed for; persists the stream when a fee is paid
+    fn pay_relayer_fee(env: &Env, stream_id: u64, stream: &mut Stream, relayer: &Address, fee: i128, max_fee: i128) -> Result<(), StreamError> {
+        if fee < 0 || fee > max_fee {
+            return Err(StreamError::FeeExceedsMax);
+        }
+        if fee == 0 {
+            return Ok(());
+        }
+
+        let recipient = stream.recipient.clone();
+        if fee > Self::available_to(env, stream_id, stream, &recipient)? {
+            return Err(StreamError::WithdrawExceedsAvailable);
+        }
+
+        let token = soroban_sdk::token::Client::new(env, &stream.token_address);
+        token.transfer(&env.current_contract_address(), relayer, &fee);
+        // Booked against the recipient's earnings, but `relayer_paid` is the only event for it
+        Self::book_payout(env, stream_id, stream, &recipient, fee)?;
+
+        env.events().publish(
+            (Symbol::new(env, "relayer_paid"), relayer.clone(), stream_id),
+            fee,
+        );
+
+        Ok(())
+    }
+
+    /// Opens a stopwatch session; `actor` is who the event attributes it to
+    fn begin_session(env: &Env, stream_id: u64, stream: &mut Stream, actor: Address) -> Result<(), StreamError> {
+        if stream.mode != AccrualMode::Stopwatch {
+            return Err(StreamError::UnsupportedAccrualMode);
+        }
+        if stream.paused_at.is_some() {
+            return Err(StreamError::StreamOnHold);
+        }
+
+        // Close out a stale session before opening a new one
+        Self::settle(env, stream_id, stream)?;
+        Self::transition(env, stream_id, stream, StreamStatus::Active)?;
+
+        let now = env.ledger().timestamp();
+        stream.last_update_time = now;
+        stream.session_start = now;
+        stream.last_heartbeat = now;
+        stream.session_seconds = 0;
+        stream.session_amount = 0;
+
+        env.events().publish(
+            (Symbol::new(env, "work_started"), actor, stream_id),
+            (),
+        );
+
+        Ok(())
+    }
+
+    /// Closes the running stopwatch session, finalizing its earnings
+    fn end_session(env: &Env, stream_id: u64, stream: &mut Stream, actor: Address) -> Result<(), StreamError> {
+        if stream.mode != AccrualMode::Stopwatch {
+            return Err(StreamError::UnsupportedAccrualMode);
+        }
+
+        // Completed -> Paused is reserved for top-ups reopening a capped stream
+        if stream.status == StreamStatus::Completed {
+            return Err(StreamError::StreamNotRunning);
+        }
+
+        Self::update_accrual(env, stream)?;
+        Self::transition(env, stream_id, stream, StreamStatus::Paused)?;
+        Self::settle(env, stream_id, stream)?;
+
+        env.events().publish(
+            (Symbol::new(env, "work_stopped"), actor, stream_id),
+            stream.total_accrued,
+        );
+
+        Ok(())
+    }
+
+    /// Records proof of life for the running session
+    fn extend_session(env: &Env, stream_id: u64, stream: &mut Stream, actor: Address) -> Result<(), StreamError> {
+        if stream.mode != AccrualMode::Stopwatch {
+            return Err(StreamError::UnsupportedAccrualMode);
+        }
+
+        // A session that already went stale is settled; the builder has to start_work again
+        Self::settle(env, stream_id, stream)?;
+        if stream.status != StreamStatus::Active {
+            return Err(StreamError::StreamNotRunning);
+        }
+
+        stream.last_heartbeat = env.ledger().timestamp();
+
+        env.events().publish(
+            (Symbol::new(env, "heartbeat"), actor, stream_id),
+            stream.last_heartbeat,
+        );
+
+        Ok(())
+    }
+
+    /// Recipient lets `operator` run the clock (start/stop/heartbeat, never withdraw) on one
+    /// stream, or on all of their streams when `stream_id` is None
+    pub fn set_operator(env: Env, recipient: Address, operator: Address, stream_id: Option<u64>) -> Result<(), StreamError> {
+        recipient.require_auth();
+
+        match stream_id {
+            Some(id) => {
+                Self::require_recipient(&env, id, &recipient)?;
+                env.storage().persistent().set(&DataKey::StreamOperator(id, operator.clone()), &recipient);
+            }
+            None => env.storage().persistent().set(&DataKey::Operator(recipient.clone(), operator.clone()), &true),
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_set"), recipient, operator),
+            stream_id,
+        );
+
+        Ok(())
+    }
+
+    /// Recipient withdraws an operator's permission granted with `set_operator`
+    pub fn revoke_operator(env: Env, recipient: Address, operator: Address, stream_id: Option<u64>) -> Result<(), StreamError> {
+        recipient.require_auth();
+
+        match stream_id {
+            Some(id) => {
+                Self::require_recipient(&env, id, &recipient)?;
+                env.storage().persistent().remove(&DataKey::StreamOperator(id, operator.clone()));
+            }
+            None => env.storage().persistent().remove(&DataKey::Operator(recipient.clone(), operator.clone())),
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_revoked"), recipient, operator),
+            stream_id,
+        );
+
+        Ok(())
+    }
+
+    /// Helper to check that `recipient` currently receives the stream
+    fn require_recipient(env: &Env, stream_id: u64, recipient: &Address) -> Result<(), StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+        if stream.recipient != *recipient {
+            return Err(StreamError::UnauthorizedRecipient);
+        }
+        Ok(())
+    }
+
+    /// Whether `operator` may currently run the stream's clock for its recipient
+    fn is_operator(env: &Env, stream_id: u64, stream: &Stream, operator: &Address) -> bool {
+        // Per-stream grants only hold for the recipient who made them
+        let granted_by: Option<Address> = env.storage().persistent().get(&DataKey::StreamOperator(stream_id, operator.clone()));
+        granted_by.as_ref() == Some(&stream.recipient)
+            || env.storage().persistent().has(&DataKey::Operator(stream.recipient.clone(), operator.clone()))
+    }
+
+    /// Requires `caller`'s auth and that they are the recipient or one of their operators
+    fn authorize_clock(env: &Env, stream_id: u64, stream: &Stream, caller: &Address) -> Result<(), StreamError> {
+        caller.require_auth();
+
+        if *caller != stream.recipient && !Self::is_operator(env, stream_id, stream, caller) {
+            return Err(StreamError::UnauthorizedOperator);
+        }
+        Ok(())
+    }
+
+    /// Withdraws earned funds (the first recipient's share on split streams)
+    pub fn withdraw(env: Env, stream_id: u64, amount: i128) -> Result<(), StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+        let recipient = stream.recipient.clone();
+
+        Self::withdraw_from(&env, stream_id, stream, recipient.clone(), Some(amount), recipient)?;
+        Ok(())
+    }
+
+    /// Withdraws everything available right now and returns the amount paid
+    pub fn withdraw_max(env: Env, stream_id: u64) -> Result<i128, StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+        let recipient = stream.recipient.clone();
+
+        Self::withdraw_from(&env, stream_id, stream, recipient.clone(), None, recipient)
+    }
+
+    /// Withdraws earned funds to another address (e.g. a cold wallet); the recipient still authorises it
+    pub fn withdraw_to(env: Env, stream_id: u64, amount: i128, destination: Address) -> Result<(), StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+        let recipient = stream.recipient.clone();
+
+        Self::withdraw_from(&env, stream_id, stream, recipient, Some(amount), destination)?;
+        Ok(())
+    }
+
+    /// Withdraws everything available to another address and returns the amount paid
+    pub fn withdraw_max_to(env: Env, stream_id: u64, destination: Address) -> Result<i128, StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+        let recipient = stream.recipient.clone();
+
+        Self::withdraw_from(&env, stream_id, stream, recipient, None, destination)
+    }
+
+    /// Withdraws a recipient's own earned share of a split stream
+    pub fn withdraw_share(env: Env, stream_id: u64, recipient: Address, amount: i128) -> Result<(), StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+        Self::withdraw_from(&env, stream_id, stream, recipient.clone(), Some(amount), recipient)?;
+        Ok(())
+    }
+
+    /// Withdraws everything available to `recipient` across the given streams (all of their
+    /// streams when `stream_ids` is None), with one transfer per token
+    pub fn withdraw_many(
+        env: Env,
+        recipient: Address,
+        stream_ids: Option<Vec<u64>>,
+    ) -> Result<Vec<WithdrawalReceipt>, StreamError> {
+        recipient.require_auth();
+
+        let stream_ids = match stream_ids {
+            Some(ids) => ids,
+            None => env.storage().persistent().get(&DataKey::RecipientStreams(recipient.clone())).unwrap_or(Vec::new(&env)),
+        };
+
+        let mut receipts = Vec::new(&env);
+        let mut totals: Vec<(Address, i128)> = Vec::new(&env);
+        for stream_id in stream_ids.iter() {
+            let mut stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+            // Streams that can't pay out yet are settled and reported with a zero amount
+            let amount = if stream.status == StreamStatus::Cancelled {
+                0
+            } else {
+                match Self::available_to(&env, stream_id, &mut stream, &recipient) {
+                    Ok(available) => available.max(0),
+                    Err(StreamError::StreamNotStarted | StreamError::CliffNotReached | StreamError::AwaitingApproval) => 0,
+                    Err(e) => return Err(e),
+                }
+            };
+
+            if amount > 0 {
+                Self::record_withdrawal(&env, stream_id, &mut stream, &recipient, amount)?;
+
+                match totals.iter().position(|(token, _)| token == stream.token_address) {
+                    Some(i) => {
+                        let (token, total) = totals.get(i as u32).ok_or(StreamError::Overflow)?;
+                        totals.set(i as u32, (token, total.checked_add(amount).ok_or(StreamError::Overflow)?));
+                    }
+                    None => totals.push_back((stream.token_address.clone(), amount)),
+                }
+            } else if stream.status != StreamStatus::Cancelled {
+                env.storage().persistent().set(&DataKey::Stream(stream_id), &stream);
+            }
+
+            receipts.push_back(WithdrawalReceipt {
+                stream_id,
+                token_address: stream.token_address,
+                amount,
+            });
+        }
+
+        for (token_address, total) in totals.iter() {
+            let token = soroban_sdk::token::Client::new(&env, &token_address);
+            token.transfer(&env.current_contract_address(), &recipient, &total);
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "withdraw_many"), recipient),
+            totals,
+        );
+
+        Ok(receipts)
+    }
+
+    /// Helper to pay `amount` (everything available when None) of what `recipient` has earned
+    /// on the stream to `destination`; returns the amount paid
+    fn withdraw_from(
+        env: &Env,
+        stream_id: u64,
+        mut stream: Stream,
+        recipient: Address,
+        amount: Option<i128>,
+        destination: Address,
+    ) -> Result<i128, StreamError> {
+        recipient.require_auth();
+
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+        if amount.is_some_and(|amount| amount <= 0) {
+            return Err(StreamError::InvalidAmount);
+        }
+
+        let available = Self::available_to(env, stream_id, &mut stream, &recipient)?;
+        if available <= 0 {
+            return Err(StreamError::NothingToWithdraw);
+        }
+        let amount = amount.unwrap_or(available);
+        if amount > available {
+            return Err(StreamError::WithdrawExceedsAvailable);
+        }
+
+        let token = soroban_sdk::token::Client::new(env, &stream.token_address);
+        token.transfer(&env.current_contract_address(), &destination, &amount);
+
+        Self::record_withdrawal(env, stream_id, &mut stream, &recipient, amount)?;
+
+        if destination != recipient {
+            env.events().publish(
+                (Symbol::new(env, "withdrawn_to"), recipient, stream_id),
+                (destination, amount),
+            );
+        }
+
+        Ok(amount)
+    }
+
+    /// Settles the stream and returns what `recipient` can withdraw from it right now
+    fn available_to(env: &Env, stream_id: u64, stream: &mut Stream, recipient: &Address) -> Result<i128, StreamError> {
+        // Update accrual to get latest earnings
+        Self::settle(env, stream_id, stream)?;
+
+        if stream.mode == AccrualMode::OneTime && stream.awaiting_approval {
+            return Err(StreamError::AwaitingApproval);
+        }
+        if (stream.mode == AccrualMode::Linear || stream.mode == AccrualMode::OneTime)
+            && stream.status == StreamStatus::Pending
+        {
+            return Err(StreamError::StreamNotStarted);
+        }
+
+        if stream.split {
+            let splits: Vec<Split> = env.storage().persistent().get(&DataKey::Splits(stream_id)).ok_or(StreamError::StreamNotFound)?;
+            let member = splits.iter().position(|s| s.recipient == *recipient).ok_or(StreamError::UnauthorizedRecipient)? as u32;
+            if !Self::cliff_reached(env, stream) {
+                return Err(StreamError::CliffNotReached);
+            }
+            return Self::split_earned(Self::reviewed_accrued(stream)?, &splits, member)?
+                .checked_sub(splits.get(member).ok_or(StreamError::InvalidSplit)?.withdrawn)
+                .ok_or(StreamError::Overflow);
+        }
+
+        if *recipient != stream.recipient {
+            return Err(StreamError::UnauthorizedRecipient);
+        }
+        if !Self::cliff_reached(env, stream) {
+            return Err(StreamError::CliffNotReached);
+        }
+
+        // Calculate available to withdraw
+        // Available = Total Accrued - Already Withdrawn
+        // Already Withdrawn = Deposit - Remaining Balance
+        // So: Available = Total Accrued - (Deposit - Remaining Balance)
+        //               = Total Accrued - Deposit + Remaining Balance
+        // Only reviewed time counts: Available = Reviewed Accrued - Already Withdrawn
+        let withdrawn = stream.deposit.checked_sub(stream.remaining_balance).ok_or(StreamError::Overflow)?;
+        Self::reviewed_accrued(stream)?.checked_sub(withdrawn).ok_or(StreamError::Overflow)
+    }
+
+    /// Books a payout the caller has transferred (or will transfer) to `recipient`, persists the stream
+    /// and emits the `withdraw` event
+    fn record_withdrawal(env: &Env, stream_id: u64, stream: &mut Stream, recipient: &Address, amount: i128) -> Result<(), StreamError> {
+        Self::book_payout(env, stream_id, stream, recipient, amount)?;
+
+        env.events().publish(
+            (Symbol::new(env, "withdraw"), recipient.clone(), stream_id),
+            amount,
+        );
+
+        Ok(())
+    }
+
+    /// Helper to charge a payout to `recipient`'s earnings and persist the stream, without an event
+    fn book_payout(env: &Env, stream_id: u64, stream: &mut Stream, recipient: &Address, amount: i128) -> Result<(), StreamError> {
+        stream.remaining_balance = stream.remaining_balance.checked_sub(amount).ok_or(StreamError::Overflow)?;
+        let mut paid_in_full = stream.status == StreamStatus::Completed && stream.remaining_balance == 0;
+        if stream.split {
+            let splits_key = DataKey::Splits(stream_id);
+            let mut splits: Vec<Split> = env.storage().persistent().get(&splits_key).ok_or(StreamError::StreamNotFound)?;
+            let member = splits.iter().position(|s| s.recipient == *recipient).ok_or(StreamError::UnauthorizedRecipient)? as u32;
+            let mut split = splits.get(member).ok_or(StreamError::InvalidSplit)?;
+            split.withdrawn = split.withdrawn.checked_add(amount).ok_or(StreamError::Overflow)?;
+            paid_in_full = stream.status == StreamStatus::Completed
+                && split.withdrawn >= Self::split_earned(stream.total_accrued, &splits, member)?;
+            splits.set(member, split);
+            env.storage().persistent().set(&splits_key, &splits);
+        }
+
+        // A finished stream drops out of `withdraw_many` once the recipient has been paid everything
+        if paid_in_full {
+            Self::unindex_recipient(env, recipient, stream_id);
+        }
+
+        env.storage().persistent().set(&DataKey::Stream(stream_id), stream);
+
+        Ok(())
+    }
+
+    /// Recipient proposes handing the stream to a new address; `settle_accrued` pays them what
+    /// is withdrawable at acceptance, otherwise unwithdrawn earnings go with the stream
+    pub fn propose_recipient_transfer(env: Env, stream_id: u64, new_recipient: Address, settle_accrued: bool) -> Result<(), StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+        stream.recipient.require_auth();
+
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+        if stream.split {
+            return Err(StreamError::InvalidSplit);
+        }
+        if new_recipient == stream.sender {
+            return Err(StreamError::SenderCannotBeRecipient);
+        }
+
+        let transfer = RecipientTransfer { new_recipient: new_recipient.clone(), settle_accrued };
+        env.storage().persistent().set(&DataKey::PendingTransfer(stream_id), &transfer);
+
+        env.events().publish(
+            (Symbol::new(&env, "transfer_proposed"), stream.recipient, stream_id),
+            (new_recipient, settle_accrued),
+        );
+
+        Ok(())
+    }
+
+    /// Proposed recipient accepts the stream; from now on they control the clock and withdrawals
+    pub fn accept_recipient_transfer(env: Env, stream_id: u64) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+        let transfer: RecipientTransfer = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingTransfer(stream_id))
+            .ok_or(StreamError::NoPendingTransfer)?;
+
+        transfer.new_recipient.require_auth();
+
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+
+        let settled = if transfer.settle_accrued {
+            // Time still under review can't be paid yet, and settling must not hand it over unpaid
+            Self::settle(&env, stream_id, &mut stream)?;
+            if Self::reviewed_accrued(&stream)? < stream.total_accrued {
+                return Err(StreamError::AwaitingApproval);
+            }
+            Self::pay_out_available(&env, stream_id, &mut stream)?
+        } else {
+            Self::settle(&env, stream_id, &mut stream)?;
+            0
+        };
+
+        let old_recipient = stream.recipient.clone();
+        Self::swap_recipient(&env, stream_id, &mut stream, transfer.new_recipient.clone());
+        env.storage().persistent().remove(&DataKey::PendingTransfer(stream_id));
+
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "recipient_transferred"), old_recipient, stream_id),
+            (transfer.new_recipient, settled),
+        );
+
+        Ok(())
+    }
+
+    /// Admin hands the stream to a different builder: the running session is stopped, the old
+    /// builder is paid what they can withdraw (before the cliff, what `cliff.on_cancel` grants them)
+    /// and the new one continues on the remaining budget
+    pub fn reassign_recipient(env: Env, stream_id: u64, new_recipient: Address) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        stream.sender.require_auth();
+
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+        if stream.split {
+            return Err(StreamError::InvalidSplit);
+        }
+        if new_recipient == stream.sender {
+            return Err(StreamError::SenderCannotBeRecipient);
+        }
+
+        Self::settle(&env, stream_id, &mut stream)?;
+        if stream.mode == AccrualMode::Stopwatch && stream.status == StreamStatus::Active {
+            Self::transition(&env, stream_id, &mut stream, StreamStatus::Paused)?;
+        }
+
+        // Reassigning signs off the old builder's timesheet so none of their time moves to the new one
+        Self::approve_sessions_until(&env, stream_id, &mut stream, u32::MAX, false)?;
+        let paid = if Self::cliff_reached(&env, &stream) {
+            // An unapproved one-time payment hasn't been earned, so it stays with the stream
+            match Self::pay_out_available(&env, stream_id, &mut stream) {
+                Err(StreamError::AwaitingApproval) => 0,
+                paid => paid?,
+            }
+        } else {
+            Self::close_out_before_cliff(&env, stream_id, &mut stream)?
+        };
+
+        let old_recipient = stream.recipient.clone();
+        Self::swap_recipient(&env, stream_id, &mut stream, new_recipient.clone());
+        // The new builder's caps and overtime threshold count only their own hours
+        stream.session_start = 0;
+        stream.last_heartbeat = 0;
+        stream.day_usage = PeriodUsage::default();
+        stream.week_usage = PeriodUsage::default();
+        env.storage().persistent().remove(&DataKey::PendingTransfer(stream_id));
+
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "recipient_reassigned"), stream.sender, stream_id),
+            (old_recipient, new_recipient, paid),
+        );
+
+        Ok(())
+    }
+
+    /// Helper to pay the current recipient whatever they can withdraw right now (0 before the
+    /// stream starts); a cliff or pending approval is returned as an error rather than paid as 0.
+    /// The caller persists the stream
+    fn pay_out_available(env: &Env, stream_id: u64, stream: &mut Stream) -> Result<i128, StreamError> {
+        let recipient = stream.recipient.clone();
+        let available = match Self::available_to(env, stream_id, stream, &recipient) {
+            Ok(available) => available.max(0),
+            Err(StreamError::StreamNotStarted) => 0,
+            Err(e) => return Err(e),
+        };
+
+        if available > 0 {
+            let token = soroban_sdk::token::Client::new(env, &stream.token_address);
+            token.transfer(&env.current_contract_address(), &recipient, &available);
+            Self::record_withdrawal(env, stream_id, stream, &recipient, available)?;
+        }
+
+        Ok(available)
+    }
+
+    /// Helper to settle a departing recipient's earnings before the cliff the way cancelling would:
+    /// pro-rata pays them out, forfeit refunds them to the sender and shrinks the budget.
+    /// Returns the amount paid to the recipient; the caller persists the stream
+    fn close_out_before_cliff(env: &Env, stream_id: u64, stream: &mut Stream) -> Result<i128, StreamError> {
+        let withdrawn = stream.deposit.checked_sub(stream.remaining_balance).ok_or(StreamError::Overflow)?;
+        let owed = stream.total_accrued.checked_sub(withdrawn).ok_or(StreamError::Overflow)?;
+        if owed <= 0 {
+            return Ok(0);
+        }
+
+        let token = soroban_sdk::token::Client::new(env, &stream.token_address);
+        if stream.cliff.on_cancel == CliffCancelPolicy::ProRata {
+            let recipient = stream.recipient.clone();
+            token.transfer(&env.current_contract_address(), &recipient, &owed);
+            Self::record_withdrawal(env, stream_id, stream, &recipient, owed)?;
+            return Ok(owed);
+        }
+
+        token.transfer(&env.current_contract_address(), &stream.sender, &owed);
+        stream.deposit = stream.deposit.checked_sub(owed).ok_or(StreamError::Overflow)?;
+        stream.remaining_balance = stream.remaining_balance.checked_sub(owed).ok_or(StreamError::Overflow)?;
+        stream.total_accrued = withdrawn;
+
+        // Linear vesting restarts from now on the smaller budget, or the forfeited share would vest again.
+        // A hold in progress moves the end time back so the unvested part keeps its full duration
+        if stream.mode == AccrualMode::Linear {
+            let now = env.ledger().timestamp();
+            if let Some(paused_at) = stream.paused_at {
+                let held = now.saturating_sub(paused_at.max(stream.start_time));
+                stream.stop_time = stream.stop_time.checked_add(held).ok_or(StreamError::Overflow)?;
+            }
+            stream.start_time = now;
+            stream.paused_duration = 0;
+            stream.paused_at = stream.paused_at.map(|_| now);
+            stream.last_update_time = now;
+        }
+
+        env.events().publish(
+            (Symbol::new(env, "earnings_forfeited"), stream.recipient.clone(), stream_id),
+            owed,
+        );
+
+        Ok(0)
+    }
+
+    /// Helper to hand a stream to a new recipient, keeping the `withdraw_many` index in sync
+    fn swap_recipient(env: &Env, stream_id: u64, stream: &mut Stream, new_recipient: Address) {
+        Self::unindex_recipient(env, &stream.recipient, stream_id);
+        Self::index_recipient(env, &new_recipient, stream_id);
+        stream.recipient = new_recipient;
+    }
+
+    /// Admin cancels stream, refunds unearned budget
+    pub fn cancel_stream(env: Env, stream_id: u64) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        stream.sender.require_auth();
+
+        // Finalize accrual
+        Self::settle(&env, stream_id, &mut stream)?;
+        Self::transition(&env, stream_id, &mut stream, StreamStatus::Cancelled)?;
+
+        // Cancelling before the cliff under a forfeit policy voids the unwithdrawable earnings
+        if stream.cliff.on_cancel == CliffCancelPolicy::Forfeit && !Self::cliff_reached(&env, &stream) {
+            stream.total_accrued = stream.deposit.checked_sub(stream.remaining_balance).ok_or(StreamError::Overflow)?;
+        }
+
+        // Time still under review is paid like approved time: disputed sessions have to be
+        // rejected with `reject_sessions` first, a cancellation doesn't claw back logged hours
+        // Calculate refund
+        // Builder keeps: Total Accrued
+        // Admin gets: Remaining Balance - (Total Accrued - Already Withdrawn)
+        //           = Remaining Balance - Total Accrued + Already Withdrawn
+        //           = Remaining Balance - Total Accrued + (Deposit - Remaining Balance)
+        //           = Deposit - Total Accrued
+        // Wait, simpler:
+        // Contract holds: Remaining Balance
+        // Builder owns: (Total Accrued - Already Withdrawn)
+        // Admin owns: Remaining Balance - Builder's Share
+        
+        let withdrawn = stream.deposit.checked_sub(stream.remaining_balance).ok_or(StreamError::Overflow)?;
+        let builder_share_remaining = stream.total_accrued.checked_sub(withdrawn).ok_or(StreamError::Overflow)?;
+        let admin_refund = stream.remaining_balance.checked_sub(builder_share_remaining).ok_or(StreamError::Overflow)?;
+
+        let token = soroban_sdk::token::Client::new(&env, &stream.token_address);
+
+        // Send builder their share; on split streams every recipient gets their accrued share.
+        // Everyone is paid off here, so the stream leaves their `withdraw_many` index
+        if stream.split {
+            let splits_key = DataKey::Splits(stream_id);
+            let mut splits: Vec<Split> = env.storage().persistent().get(&splits_key).ok_or(StreamError::StreamNotFound)?;
+            for i in 0..splits.len() {
+                let mut split = splits.get(i).ok_or(StreamError::InvalidSplit)?;
+                Self::unindex_recipient(&env, &split.recipient, stream_id);
+                let owed = Self::split_earned(stream.total_accrued, &splits, i)?.checked_sub(split.withdrawn).ok_or(StreamError::Overflow)?;
+                if owed > 0 {
+                    token.transfer(&env.current_contract_address(), &split.recipient, &owed);
+                    split.withdrawn += owed;
+                    splits.set(i, split);
+                }
+            }
+            env.storage().persistent().set(&splits_key, &splits);
+        } else {
+            Self::unindex_recipient(&env, &stream.recipient, stream_id);
+            if builder_share_remaining > 0 {
+                token.transfer(&env.current_contract_address(), &stream.recipient, &builder_share_remaining);
+            }
+        }
+
+        // Send admin refund
+        if admin_refund > 0 {
+            token.transfer(&env.current_contract_address(), &stream.sender, &admin_refund);
+        }
+
+        stream.remaining_balance = 0;
+        
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "cancelled"), stream.sender, stream_id),
+            admin_refund,
+        );
+
+        Ok(())
+    }
+
+    /// Admin adds budget to a stream, reopening it if the old budget was used up
+    pub fn top_up_stream(env: Env, stream_id: u64, amount: i128) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        stream.sender.require_auth();
+
+        // Milestone, hybrid and one-time budgets are fixed by their payout terms, and linear vesting
+        // is a fraction of the deposit, so growing it would vest part of the top-up retroactively
+        if stream.mode != AccrualMode::Stopwatch {
+            return Err(StreamError::UnsupportedAccrualMode);
+        }
+        if amount <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+
+        // Settle against the old budget first so a running clock can't claim the new funds retroactively
+        Self::settle(&env, stream_id, &mut stream)?;
+
+        let token = soroban_sdk::token::Client::new(&env, &stream.token_address);
+        token.transfer(&stream.sender, &env.current_contract_address(), &amount);
+
+        let old_deposit = stream.deposit;
+        stream.deposit = stream.deposit.checked_add(amount).ok_or(StreamError::Overflow)?;
+        stream.remaining_balance = stream.remaining_balance.checked_add(amount).ok_or(StreamError::Overflow)?;
+
+        // A stream that hit its cap can be clocked into again, and goes back into `withdraw_many`
+        // for recipients who had already been paid in full
+        if stream.status == StreamStatus::Completed {
+            Self::transition(&env, stream_id, &mut stream, StreamStatus::Paused)?;
+            if stream.split {
+                let splits: Vec<Split> = env.storage().persistent().get(&DataKey::Splits(stream_id)).ok_or(StreamError::StreamNotFound)?;
+                for split in splits.iter() {
+                    Self::index_recipient(&env, &split.recipient, stream_id);
+                }
+            } else {
+                Self::index_recipient(&env, &stream.recipient, stream_id);
+            }
+        }
+        Self::settle(&env, stream_id, &mut stream)?;
+
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "topped_up"), stream.sender, stream_id),
+            (old_deposit, stream.deposit, amount),
+        );
+
+        Ok(())
+    }
+
+    /// Admin refunds part of the unearned budget while keeping the stream alive
+    pub fn reduce_deposit(env: Env, stream_id: u64, amount: i128) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        stream.sender.require_auth();
+
+        // Only stopwatch budgets can shrink: linear vesting is a fraction of the deposit, so cutting
+        // it would claw back vested funds, and milestone, hybrid and one-time budgets are fixed by
+        // their payout terms
+        if stream.mode != AccrualMode::Stopwatch {
+            return Err(StreamError::UnsupportedAccrualMode);
+        }
+        if amount <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+
+        // Settle the running session so the floor is what has been earned right now
+        Self::settle(&env, stream_id, &mut stream)?;
+
+        let unearned = stream.deposit.checked_sub(stream.total_accrued).ok_or(StreamError::Overflow)?;
+        if amount > unearned {
+            return Err(StreamError::RefundExceedsUnearned);
+        }
+
+        let old_deposit = stream.deposit;
+        stream.deposit = stream.deposit.checked_sub(amount).ok_or(StreamError::Overflow)?;
+        stream.remaining_balance = stream.remaining_balance.checked_sub(amount).ok_or(StreamError::Overflow)?;
+
+        // Cutting down to exactly what was earned completes the stream
+        Self::settle(&env, stream_id, &mut stream)?;
+
+        let token = soroban_sdk::token::Client::new(&env, &stream.token_address);
+        token.transfer(&env.current_contract_address(), &stream.sender, &amount);
+
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "deposit_reduced"), stream.sender, stream_id),
+            (old_deposit, stream.deposit, amount),
+        );
+
+        Ok(())
+    }
+
+    /// Admin puts the stream on hold: any running session is stopped and settled,
+    /// and the builder cannot start work again until the stream is resumed
+    pub fn pause_stream(env: Env, stream_id: u64) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        stream.sender.require_auth();
+
+        if stream.paused_at.is_some() {
+            return Err(StreamError::StreamOnHold);
+        }
+
+        Self::settle(&env, stream_id, &mut stream)?;
+
+        match stream.status {
+            StreamStatus::Active => Self::transition(&env, stream_id, &mut stream, StreamStatus::Paused)?,
+            StreamStatus::Pending | StreamStatus::Paused => {}
+            StreamStatus::Completed => return Err(StreamError::InvalidStateTransition),
+            StreamStatus::Cancelled => return Err(StreamError::StreamCancelled),
+        }
+
+        let paused_at = env.ledger().timestamp();
+        stream.paused_at = Some(paused_at);
+
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "paused"), stream.sender, stream_id),
+            (paused_at, stream.paused_duration),
+        );
+
+        Ok(())
+    }
+
+    /// Admin lifts a hold; linear streams have their end time pushed back by the time spent on hold
+    pub fn resume_stream(env: Env, stream_id: u64) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        stream.sender.require_auth();
+
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+        let paused_at = stream.paused_at.ok_or(StreamError::StreamNotOnHold)?;
+
+        let resumed_at = env.ledger().timestamp();
+        let held = match stream.mode {
+            // Only time on hold after vesting began delays the schedule
+            AccrualMode::Linear => resumed_at.saturating_sub(paused_at.max(stream.start_time)),
+            _ => resumed_at.saturating_sub(paused_at),
+        };
+
+        stream.paused_at = None;
+        stream.paused_duration = stream.paused_duration.checked_add(held).ok_or(StreamError::Overflow)?;
+        if stream.mode == AccrualMode::Linear {
+            stream.stop_time = stream.stop_time.checked_add(held).ok_or(StreamError::Overflow)?;
+        }
+        // Stopwatch builders restart their own clock; other streams pick up where they left off
+        if stream.mode != AccrualMode::Stopwatch && stream.status == StreamStatus::Paused {
+            Self::transition(&env, stream_id, &mut stream, StreamStatus::Active)?;
+        }
+        Self::settle(&env, stream_id, &mut stream)?;
+
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "resumed"), stream.sender, stream_id),
+            (paused_at, resumed_at, stream.paused_duration),
+        );
+
+        Ok(())
+    }
+
+    /// Admin records the review of a HYBRID stream: the escrowed remainder is scaled by
+    /// `multiplier_bps` (at most 100%) for the builder and the difference is refunded
+    pub fn record_review(env: Env, stream_id: u64, multiplier_bps: u32) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        stream.sender.require_auth();
+
+        if stream.mode != AccrualMode::Hybrid {
+            return Err(StreamError::UnsupportedAccrualMode);
+        }
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+        if stream.review_multiplier_bps.is_some() {
+            return Err(StreamError::ReviewAlreadyRecorded);
+        }
+        if multiplier_bps as i128 > BPS_DENOMINATOR {
+            return Err(StreamError::InvalidAmount);
+        }
+
+        let upfront = stream.deposit.checked_mul(stream.upfront_bps as i128).ok_or(StreamError::Overflow)? / BPS_DENOMINATOR;
+        let remainder = stream.deposit.checked_sub(upfront).ok_or(StreamError::Overflow)?;
+        let payout = remainder.checked_mul(multiplier_bps as i128).ok_or(StreamError::Overflow)? / BPS_DENOMINATOR;
+        let refund = remainder.checked_sub(payout).ok_or(StreamError::Overflow)?;
+
+        stream.review_multiplier_bps = Some(multiplier_bps);
+        stream.total_accrued = upfront.checked_add(payout).ok_or(StreamError::Overflow)?;
+
+        // The unearned part of the remainder leaves escrow right away
+        stream.deposit = stream.deposit.checked_sub(refund).ok_or(StreamError::Overflow)?;
+        stream.remaining_balance = stream.remaining_balance.checked_sub(refund).ok_or(StreamError::Overflow)?;
+        if refund > 0 {
+            let token = soroban_sdk::token::Client::new(&env, &stream.token_address);
+            token.transfer(&env.current_contract_address(), &stream.sender, &refund);
+        }
+
+        Self::settle(&env, stream_id, &mut stream)?;
+
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "review_recorded"), stream.sender, stream_id),
+            (multiplier_bps, payout, refund),
+        );
+
+        Ok(())
+    }
+
+    /// Admin approves every session awaiting review before index `up_to`, making its time withdrawable
+    pub fn approve_sessions(env: Env, stream_id: u64, up_to: u32) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        stream.sender.require_auth();
+
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+        if up_to > stream.session_count {
+            return Err(StreamError::InvalidSession);
+        }
+
+        // Expired sessions approve themselves first, so `from` is what was still under review
+        Self::settle(&env, stream_id, &mut stream)?;
+        let from = stream.first_pending_session;
+        let released = Self::approve_sessions_until(&env, stream_id, &mut stream, up_to, false)?;
+
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "sessions_approved"), stream.sender, stream_id),
+            (from, up_to, released),
+        );
+
+        Ok(())
+    }
+
+    /// Admin rejects the sessions still under review before index `up_to`; their earnings are
+    /// refunded to the sender and taken out of the budget
+    pub fn reject_sessions(env: Env, stream_id: u64, up_to: u32) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        stream.sender.require_auth();
+
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+        if up_to > stream.session_count {
+            return Err(StreamError::InvalidSession);
+        }
+
+        // Sessions whose window ran out are approved by settling and can no longer be disputed
+        Self::settle(&env, stream_id, &mut stream)?;
+        let from = stream.first_pending_session;
+
+        let mut refunded: i128 = 0;
+        while stream.first_pending_session < up_to {
+            let session_key = DataKey::Session(stream_id, stream.first_pending_session);
+            let mut session: WorkSession = env.storage().persistent().get(&session_key).ok_or(StreamError::StreamNotFound)?;
+            session.rejected = true;
+            env.storage().persistent().set(&session_key, &session);
+
+            refunded = refunded.checked_add(session.amount).ok_or(StreamError::Overflow)?;
+            stream.first_pending_session += 1;
+        }
+
+        if refunded > 0 {
+            stream.pending_amount = stream.pending_amount.checked_sub(refunded).ok_or(StreamError::Overflow)?;
+            stream.total_accrued = stream.total_accrued.checked_sub(refunded).ok_or(StreamError::Overflow)?;
+            stream.deposit = stream.deposit.checked_sub(refunded).ok_or(StreamError::Overflow)?;
+            stream.remaining_balance = stream.remaining_balance.checked_sub(refunded).ok_or(StreamError::Overflow)?;
+
+            let token = soroban_sdk::token::Client::new(&env, &stream.token_address);
+            token.transfer(&env.current_contract_address(), &stream.sender, &refunded);
+        }
+
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "sessions_rejected"), stream.sender, stream_id),
+            (from, up_to, refunded),
+        );
+
+        Ok(())
+    }
+
+    /// Admin approves a one-time payment that was created with `requires_approval`
+    pub fn approve_payment(env: Env, stream_id: u64) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        stream.sender.require_auth();
+
+        if stream.mode != AccrualMode::OneTime {
+            return Err(StreamError::UnsupportedAccrualMode);
+        }
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+        if !stream.awaiting_approval {
+            return Err(StreamError::ApprovalNotRequired);
+        }
+
+        stream.awaiting_approval = false;
+        Self::settle(&env, stream_id, &mut stream)?;
+
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "payment_approved"), stream.sender, stream_id),
+            stream.deposit,
+        );
+
+        Ok(())
+    }
+
+    /// Builder submits proof for the next milestone in order (or resubmits a rejected one)
+    pub fn submit_milestone(env: Env, stream_id: u64, index: u32, proof_hash: BytesN<32>) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        stream.recipient.require_auth();
+
+        if stream.mode != AccrualMode::Milestone {
+            return Err(StreamError::UnsupportedAccrualMode);
+        }
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+        if stream.paused_at.is_some() {
+            return Err(StreamError::StreamOnHold);
+        }
+
+        let milestones_key = DataKey::Milestones(stream_id);
+        let mut milestones: Vec<Milestone> = env.storage().persistent().get(&milestones_key).ok_or(StreamError::StreamNotFound)?;
+
+        // Milestones are delivered in order: only the first unapproved one can be submitted
+        let next = milestones.iter().position(|m| m.status != MilestoneStatus::Approved);
+        if next != Some(index as usize) {
+            return Err(StreamError::InvalidMilestone);
+        }
+
+        let mut milestone = milestones.get(index).ok_or(StreamError::InvalidMilestone)?;
+        if milestone.status == MilestoneStatus::Submitted {
+            return Err(StreamError::MilestoneAlreadySubmitted);
+        }
+        milestone.status = MilestoneStatus::Submitted;
+        milestone.proof_hash = proof_hash.clone();
+        milestones.set(index, milestone);
+
+        if stream.status == StreamStatus::Pending {
+            Self::transition(&env, stream_id, &mut stream, StreamStatus::Active)?;
+        }
+
+        env.storage().persistent().set(&milestones_key, &milestones);
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "milestone_submitted"), stream.recipient, stream_id),
+            (index, proof_hash),
+        );
+
+        Ok(())
+    }
+
+    /// Admin approves a submitted milestone, making its amount withdrawable
+    pub fn approve_milestone(env: Env, stream_id: u64, index: u32) -> Result<(), StreamError> {
+        let key = DataKey::Stream(stream_id);
+        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+
+        stream.sender.require_auth();
+
+        let mut milestone = Self::submitted_milestone(&env, &stream, stream_id, index)?;
+        milestone.status = MilestoneStatus::Approved;
+        let amount = milestone.amount;
+        Self::store_milestone(&env, stream_id, index, milestone)?;
+
+        stream.total_accrued = stream.total_accrued.checked_add(amount).ok_or(StreamError::Overflow)?;
+
+        // Approving the last milestone completes the stream
+        Self::settle(&env, stream_id, &mut stream)?;
+
+        env.storage().persistent().set(&key, &stream);
+
+        env.events().publish(
+            (Symbol::new(&env, "milestone_approved"), stream.sender, stream_id),
+            (index, amount),
+        );
+
+        Ok(())
+    }
+
+    /// Admin sends a submitted milestone back; the builder may resubmit it
+    pub fn reject_milestone(env: Env, stream_id: u64, index: u32) -> Result<(), StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+        stream.sender.require_auth();
+
+        let mut milestone = Self::submitted_milestone(&env, &stream, stream_id, index)?;
+        milestone.status = MilestoneStatus::Rejected;
+        Self::store_milestone(&env, stream_id, index, milestone)?;
+
+        env.events().publish(
+            (Symbol::new(&env, "milestone_rejected"), stream.sender, stream_id),
+            index,
+        );
+
+        Ok(())
+    }
+
+    /// Creates a team pool: one escrowed budget that each member draws from on their own clock
+    pub fn create_pool(
+        env: Env,
+        sender: Address,
+        deposit: i128,
+        token_address: Address,
+        members: Vec<PoolMemberSpec>,
+    ) -> Result<u64, StreamError> {
+        sender.require_auth();
+
+        if deposit <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+        if members.is_empty() {
+            return Err(StreamError::InvalidPoolMembers);
+        }
+
+        let mut stored: Vec<PoolMember> = Vec::new(&env);
+        for spec in members.iter() {
+            if spec.rate_per_second <= 0 {
+                return Err(StreamError::InvalidAmount);
+            }
+            if spec.recipient == sender {
+                return Err(StreamError::SenderCannotBeRecipient);
+            }
+            if stored.iter().any(|m| m.recipient == spec.recipient) {
+                return Err(StreamError::InvalidPoolMembers);
+            }
+            stored.push_back(PoolMember {
+                recipient: spec.recipient,
+                rate_per_second: spec.rate_per_second,
+                running: false,
+                accrued: 0,
+                withdrawn: 0,
+            });
+        }
+
+        let token = soroban_sdk::token::Client::new(&env, &token_address);
+        token.transfer(&sender, &env.current_contract_address(), &deposit);
+
+        let pool = Pool {
+            sender: sender.clone(),
+            deposit,
+            remaining_balance: deposit,
+            total_accrued: 0,
+            last_update_time: env.ledger().timestamp(),
+            status: StreamStatus::Pending,
+            token_address,
+        };
+
+        let pool_id = env.storage().instance().get(&DataKey::NextPoolId).unwrap_or(0u64);
+        env.storage().instance().set(&DataKey::NextPoolId, &(pool_id + 1));
+        env.storage().persistent().set(&DataKey::Pool(pool_id), &pool);
+        env.storage().persistent().set(&DataKey::PoolMembers(pool_id), &stored);
+
+        env.events().publish(
+            (Symbol::new(&env, "pool_created"), sender, pool_id),
+            (deposit, stored.len()),
+        );
+
+        Ok(pool_id)
+    }
+
+    /// Pool member starts their own clock
+    pub fn pool_start_work(env: Env, pool_id: u64, member: Address) -> Result<(), StreamError> {
+        member.require_auth();
+
+        let (mut pool, mut members) = Self::load_pool(&env, pool_id)?;
+        if pool.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+
+        Self::settle_pool(&env, pool_id, &mut pool, &mut members)?;
+        if pool.status == StreamStatus::Completed {
+            return Err(StreamError::BudgetExceeded);
+        }
+
+        let index = Self::pool_member_index(&members, &member)?;
+        let mut entry = members.get(index).ok_or(StreamError::NotPoolMember)?;
+        if entry.running {
+            return Err(StreamError::StreamAlreadyRunning);
+        }
+        entry.running = true;
+        members.set(index, entry);
+        pool.status = StreamStatus::Active;
+
+        Self::store_pool(&env, pool_id, &pool, &members);
+
+        env.events().publish(
+            (Symbol::new(&env, "pool_work_started"), member, pool_id),
+            (),
+        );
+
+        Ok(())
+    }
+
+    /// Pool member stops their own clock
+    pub fn pool_stop_work(env: Env, pool_id: u64, member: Address) -> Result<(), StreamError> {
+        member.require_auth();
+
+        let (mut pool, mut members) = Self::load_pool(&env, pool_id)?;
+        if pool.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+
+        // Exhausting the budget stops every clock, so this may find the member already stopped
+        Self::settle_pool(&env, pool_id, &mut pool, &mut members)?;
+
+        let index = Self::pool_member_index(&members, &member)?;
+        let mut entry = members.get(index).ok_or(StreamError::NotPoolMember)?;
+        if !entry.running {
+            return Err(StreamError::StreamNotRunning);
+        }
+        entry.running = false;
+        let accrued = entry.accrued;
+        members.set(index, entry);
+
+        Self::store_pool(&env, pool_id, &pool, &members);
+
+        env.events().publish(
+            (Symbol::new(&env, "pool_work_stopped"), member, pool_id),
+            accrued,
+        );
+
+        Ok(())
+    }
+
+    /// Pool member withdraws from what they have earned
+    pub fn pool_withdraw(env: Env, pool_id: u64, member: Address, amount: i128) -> Result<(), StreamError> {
+        member.require_auth();
+
+        let (mut pool, mut members) = Self::load_pool(&env, pool_id)?;
+        if pool.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+        if amount <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+
+        Self::settle_pool(&env, pool_id, &mut pool, &mut members)?;
+
+        let index = Self::pool_member_index(&members, &member)?;
+        let mut entry = members.get(index).ok_or(StreamError::NotPoolMember)?;
+        let available = entry.accrued.checked_sub(entry.withdrawn).ok_or(StreamError::Overflow)?;
+        if available <= 0 {
+            return Err(StreamError::NothingToWithdraw);
+        }
+        if amount > available {
+            return Err(StreamError::WithdrawExceedsAvailable);
+        }
+
+        let token = soroban_sdk::token::Client::new(&env, &pool.token_address);
+        token.transfer(&env.current_contract_address(), &member, &amount);
+
+        entry.withdrawn = entry.withdrawn.checked_add(amount).ok_or(StreamError::Overflow)?;
+        members.set(index, entry);
+        pool.remaining_balance = pool.remaining_balance.checked_sub(amount).ok_or(StreamError::Overflow)?;
+
+        Self::store_pool(&env, pool_id, &pool, &members);
+
+        env.events().publish(
+            (Symbol::new(&env, "pool_withdraw"), member, pool_id),
+            amount,
+        );
+
+        Ok(())
+    }
+
+    /// Admin cancels a pool: every member is paid what they earned and the rest is refunded
+    pub fn cancel_pool(env: Env, pool_id: u64) -> Result<(), StreamError> {
+        let (mut pool, mut members) = Self::load_pool(&env, pool_id)?;
+
+        pool.sender.require_auth();
+
+        if pool.status == StreamStatus::Cancelled {
+            return Err(StreamError::AlreadyCanceled);
+        }
+
+        Self::settle_pool(&env, pool_id, &mut pool, &mut members)?;
+
+        let token = soroban_sdk::token::Client::new(&env, &pool.token_address);
+        let mut paid: i128 = 0;
+        for i in 0..members.len() {
+            let mut entry = members.get(i).ok_or(StreamError::NotPoolMember)?;
+            let owed = entry.accrued.checked_sub(entry.withdrawn).ok_or(StreamError::Overflow)?;
+            if owed > 0 {
+                token.transfer(&env.current_contract_address(), &entry.recipient, &owed);
+                paid = paid.checked_add(owed).ok_or(StreamError::Overflow)?;
+                entry.withdrawn = entry.accrued;
+            }
+            entry.running = false;
+            members.set(i, entry);
+        }
+
+        let admin_refund = pool.remaining_balance.checked_sub(paid).ok_or(StreamError::Overflow)?;
+        if admin_refund > 0 {
+            token.transfer(&env.current_contract_address(), &pool.sender, &admin_refund);
+        }
+
+        pool.remaining_balance = 0;
+        pool.status = StreamStatus::Cancelled;
+
+        Self::store_pool(&env, pool_id, &pool, &members);
+
+        env.events().publish(
+            (Symbol::new(&env, "pool_cancelled"), pool.sender, pool_id),
+            admin_refund,
+        );
+
+        Ok(())
+    }
+
+    /// Helper to load a pool together with its members
+    fn load_pool(env: &Env, pool_id: u64) -> Result<(Pool, Vec<PoolMember>), StreamError> {
+        let pool: Pool = env.storage().persistent().get(&DataKey::Pool(pool_id)).ok_or(StreamError::PoolNotFound)?;
+        let members: Vec<PoolMember> = env.storage().persistent().get(&DataKey::PoolMembers(pool_id)).ok_or(StreamError::PoolNotFound)?;
+        Ok((pool, members))
+    }
+
+    /// Helper to persist a pool together with its members
+    fn store_pool(env: &Env, pool_id: u64, pool: &Pool, members: &Vec<PoolMember>) {
+        env.storage().persistent().set(&DataKey::Pool(pool_id), pool);
+        env.storage().persistent().set(&DataKey::PoolMembers(pool_id), members);
+    }
+
+    fn pool_member_index(members: &Vec<PoolMember>, member: &Address) -> Result<u32, StreamError> {
+        members
+            .iter()
+            .position(|m| m.recipient == *member)
+            .map(|i| i as u32)
+            .ok_or(StreamError::NotPoolMember)
+    }
+
+    /// Brings every running clock up to date and completes the pool once the budget is used up
+    fn settle_pool(env: &Env, pool_id: u64, pool: &mut Pool, members: &mut Vec<PoolMember>) -> Result<(), StreamError> {
+        if Self::accrue_pool(env, pool, members)? {
+            pool.status = StreamStatus::Completed;
+
+            env.events().publish(
+                (Symbol::new(env, "pool_exhausted"), pool.sender.clone(), pool_id),
+                pool.total_accrued,
+            );
+        }
+        Ok(())
+    }
+
+    /// Credits every running member up to now; returns true if this used up the budget.
+    /// When the clocks together would overrun the budget, what is left is shared in
+    /// proportion to their rates and every clock is stopped.
+    fn accrue_pool(env: &Env, pool: &mut Pool, members: &mut Vec<PoolMember>) -> Result<bool, StreamError> {
+        let now = env.ledger().timestamp();
+        let elapsed = now.saturating_sub(pool.last_update_time) as i128;
+        pool.last_update_time = now;
+
+        if pool.status != StreamStatus::Active || elapsed == 0 {
+            return Ok(false);
+        }
+
+        let mut combined_rate: i128 = 0;
+        let mut last_running = None;
+        for (i, member) in members.iter().enumerate() {
+            if member.running {
+                combined_rate = combined_rate.checked_add(member.rate_per_second).ok_or(StreamError::Overflow)?;
+                last_running = Some(i as u32);
+            }
+        }
+        let last_running = match last_running {
+            Some(i) => i,
+            None => return Ok(false),
+        };
 
-#[contracttype]
-pub enum DataKey {
-    Stream(u64),
-    NextStreamId,
-}
+        let room = pool.deposit.checked_sub(pool.total_accrued).ok_or(StreamError::Overflow)?;
+        let wanted = combined_rate.checked_mul(elapsed).ok_or(StreamError::Overflow)?;
+        let exhausted = wanted >= room;
 
-#[contractimpl]
-impl StreamContract {
-    /// Creates a new job/stream with a fixed budget and rate
-    pub fn create_stream(
-        env: Env,
+        let mut credited: i128 = 0;
+        for i in 0..members.len() {
+            let mut member = members.get(i).ok_or(StreamError::NotPoolMember)?;
+            if !member.running {
+                continue;
+            }
+
+            let earned = if !exhausted {
+                member.rate_per_second * elapsed
+            } else if i == last_running {
+                // The last running member absorbs rounding dust so the budget is fully used
+                room - credited
+            } else {
+                member.rate_per_second.checked_mul(room).ok_or(StreamError::Overflow)? / combined_rate
+            };
+
+            credited += earned;
+            member.accrued = member.accrued.checked_add(earned).ok_or(StreamError::Overflow)?;
+            if exhausted {
+                member.running = false;
+            }
+            members.set(i, member);
+        }
+
+        pool.total_accrued = pool.total_accrued.checked_add(credited).ok_or(StreamError::Overflow)?;
+
+        Ok(exhausted)
+    }
+
+    /// Helper to load a milestone that is waiting for the sender's review
+    fn submitted_milestone(env: &Env, stream: &Stream, stream_id: u64, index: u32) -> Result<Milestone, StreamError> {
+        if stream.mode != AccrualMode::Milestone {
+            return Err(StreamError::UnsupportedAccrualMode);
+        }
+        if stream.status == StreamStatus::Cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+
+        let milestones: Vec<Milestone> = env.storage().persistent().get(&DataKey::Milestones(stream_id)).ok_or(StreamError::StreamNotFound)?;
+        let milestone = milestones.get(index).ok_or(StreamError::InvalidMilestone)?;
+        if milestone.status != MilestoneStatus::Submitted {
+            return Err(StreamError::MilestoneNotSubmitted);
+        }
+
+        Ok(milestone)
+    }
+
+    /// Helper to write back a single milestone
+    fn store_milestone(env: &Env, stream_id: u64, index: u32, milestone: Milestone) -> Result<(), StreamError> {
+        let key = DataKey::Milestones(stream_id);
+        let mut milestones: Vec<Milestone> = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+        milestones.set(index, milestone);
+        env.storage().persistent().set(&key, &milestones);
+        Ok(())
+    }
+
+    /// A split recipient's share of `accrued`; the last recipient absorbs rounding dust
+    fn split_earned(accrued: i128, splits: &Vec<Split>, index: u32) -> Result<i128, StreamError> {
+        let share_of = |split: Split| -> Result<i128, StreamError> {
+            Ok(accrued.checked_mul(split.share_bps as i128).ok_or(StreamError::Overflow)? / BPS_DENOMINATOR)
+        };
+
+        if index + 1 < splits.len() {
+            return share_of(splits.get(index).ok_or(StreamError::InvalidSplit)?);
+        }
+
+        let mut earned = accrued;
+        for split in splits.iter().take(index as usize) {
+            earned = earned.checked_sub(share_of(split)?).ok_or(StreamError::Overflow)?;
+        }
+        Ok(earned)
+    }
+
+    /// Helper to validate creation parameters and build a fresh stream
+    fn new_stream(
+        env: &Env,
         sender: Address,
         recipient: Address,
         deposit: i128,
         token_address: Address,
-        rate_per_second: i128,
-    ) -> Result<u64, StreamError> {
-        sender.require_auth();
-        
-        if deposit <= 0 || rate_per_second <= 0 {
+        schedule: Schedule,
+        options: StreamOptions,
+    ) -> Result<Stream, StreamError> {
+        if deposit <= 0 {
             return Err(StreamError::InvalidAmount);
         }
+        if let Schedule::Stopwatch(rate_per_second) = schedule {
+            if rate_per_second <= 0 {
+                return Err(StreamError::InvalidAmount);
+            }
+        }
         if sender == recipient {
             return Err(StreamError::SenderCannotBeRecipient);
         }
 
-        // Transfer tokens to contract
-        let token = soroban_sdk::token::Client::new(&env, &token_address);
-        token.transfer(&sender, &env.current_contract_address(), &deposit);
+        let now = env.ledger().timestamp();
+        let (mode, rate_per_second, start_time, stop_time) = match schedule {
+            Schedule::Stopwatch(rate_per_second) => (AccrualMode::Stopwatch, rate_per_second, now, 0),
+            Schedule::Linear(start_time, stop_time) => {
+                if stop_time <= start_time {
+                    return Err(StreamError::InvalidTimeRange);
+                }
+                if start_time < now.saturating_sub(MAX_START_TIME_AGE) {
+                    return Err(StreamError::InvalidStartTime);
+                }
+                (AccrualMode::Linear, 0, start_time, stop_time)
+            }
+        };
 
-        let stream = Stream {
-            sender: sender.clone(),
-            recipient: recipient.clone(),
+        if options.cliff.amount < 0 || options.cliff.amount > deposit {
+            return Err(StreamError::InvalidAmount);
+        }
+        if mode == AccrualMode::Linear && options.cliff.time > stop_time {
+            return Err(StreamError::InvalidTimeRange);
+        }
+        if options.caps.daily < 0 || options.caps.weekly < 0 || options.overtime.rate_per_second < 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+        if mode == AccrualMode::Linear
+            && (options.max_session_seconds > 0
+                || options.heartbeat_grace > 0
+                || options.caps.daily > 0
+                || options.caps.weekly > 0
+                || options.overtime.rate_per_second > 0
+                || options.review_window > 0)
+        {
+            return Err(StreamError::UnsupportedAccrualMode);
+        }
+
+        let mut stream = Self::base_stream(env, sender, recipient, deposit, token_address, mode);
+        stream.rate_per_second = rate_per_second;
+        stream.start_time = start_time;
+        stream.stop_time = stop_time;
+        stream.cliff = options.cliff;
+        stream.max_session_seconds = options.max_session_seconds;
+        stream.heartbeat_grace = options.heartbeat_grace;
+        stream.caps = options.caps;
+        stream.overtime = options.overtime;
+        stream.review_window = options.review_window;
+
+        Ok(stream)
+    }
+
+    /// Helper to build a stream with no options set
+    fn base_stream(
+        env: &Env,
+        sender: Address,
+        recipient: Address,
+        deposit: i128,
+        token_address: Address,
+        mode: AccrualMode,
+    ) -> Stream {
+        let now = env.ledger().timestamp();
+
+        Stream {
+            sender,
+            recipient,
             deposit,
-            rate_per_second,
+            mode,
+            rate_per_second: 0,
+            start_time: now,
+            stop_time: 0,
             remaining_balance: deposit,
             total_accrued: 0,
-            last_update_time: env.ledger().timestamp(),
-            is_running: false,
-            is_cancelled: false,
+            last_update_time: now,
+            status: StreamStatus::Pending,
+            paused_at: None,
+            paused_duration: 0,
+            cliff: Cliff::default(),
+            max_session_seconds: 0,
+            heartbeat_grace: 0,
+            session_start: 0,
+            last_heartbeat: 0,
+            session_seconds: 0,
+            session_amount: 0,
+            session_count: 0,
+            review_window: 0,
+            first_pending_session: 0,
+            pending_amount: 0,
+            caps: AccrualCaps::default(),
+            day_usage: PeriodUsage::default(),
+            week_usage: PeriodUsage::default(),
+            overtime: OvertimeTier::default(),
+            tier_totals: TierTotals::default(),
+            upfront_bps: 0,
+            review_multiplier_bps: None,
+            awaiting_approval: false,
+            split: false,
             token_address,
-        };
+        }
+    }
 
+    /// Whether the stream's cliff has been passed; reaching the full budget clears the amount
+    /// condition, but a future cliff time still blocks
+    fn cliff_reached(env: &Env, stream: &Stream) -> bool {
+        let min_accrued = stream.cliff.amount.min(stream.deposit);
+        env.ledger().timestamp() >= stream.cliff.time && stream.total_accrued >= min_accrued
+    }
+
+    /// Helper to transfer the escrow, assign an ID and persist a freshly built stream
+    fn open_stream(env: &Env, stream: Stream) -> u64 {
+        // Transfer tokens to contract
+        let token = soroban_sdk::token::Client::new(env, &stream.token_address);
+        token.transfer(&stream.sender, &env.current_contract_address(), &stream.deposit);
+
+        Self::register_stream(env, stream)
+    }
+
+    /// Helper to assign an ID and persist a stream whose escrow is already in the contract
+    fn register_stream(env: &Env, stream: Stream) -> u64 {
         let stream_id = env.storage().instance().get(&DataKey::NextStreamId).unwrap_or(0u64);
         env.storage().instance().set(&DataKey::NextStreamId, &(stream_id + 1));
         env.storage().persistent().set(&DataKey::Stream(stream_id), &stream);
+        Self::index_recipient(env, &stream.recipient, stream_id);
 
         env.events().publish(
-            (Symbol::new(&env, "stream_created"), sender, recipient),
+            (Symbol::new(env, "stream_created"), stream.sender, stream.recipient),
             stream_id,
         );
 
-        Ok(stream_id)
+        stream_id
     }
 
-    /// Builder starts working (clock starts ticking)
-    pub fn start_work(env: Env, stream_id: u64) -> Result<(), StreamError> {
-        let key = DataKey::Stream(stream_id);
-        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
-
-        stream.recipient.require_auth();
-
-        if stream.is_cancelled {
-            return Err(StreamError::StreamCancelled);
-        }
-        if stream.is_running {
-            return Err(StreamError::StreamAlreadyRunning);
-        }
-        // Check if budget is already exhausted
-        if stream.total_accrued >= stream.deposit {
-             return Err(StreamError::BudgetExceeded);
+    /// Helper to add a stream to a recipient's `withdraw_many` index
+    fn index_recipient(env: &Env, recipient: &Address, stream_id: u64) {
+        let key = DataKey::RecipientStreams(recipient.clone());
+        let mut stream_ids: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+        if stream_ids.contains(stream_id) {
+            return;
         }
+        stream_ids.push_back(stream_id);
+        env.storage().persistent().set(&key, &stream_ids);
+    }
 
-        stream.is_running = true;
-        stream.last_update_time = env.ledger().timestamp();
-        
-        env.storage().persistent().set(&key, &stream);
-        
-        env.events().publish(
-            (Symbol::new(&env, "work_started"), stream.recipient, stream_id),
-            (),
-        );
-
-        Ok(())
+    /// Helper to drop a stream from a recipient's `withdraw_many` index
+    fn unindex_recipient(env: &Env, recipient: &Address, stream_id: u64) {
+        let key = DataKey::RecipientStreams(recipient.clone());
+        let mut stream_ids: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+        if let Some(i) = stream_ids.first_index_of(stream_id) {
+            stream_ids.remove(i);
+            env.storage().persistent().set(&key, &stream_ids);
+        }
     }
 
-    /// Builder stops working (clock stops, earnings accrued)
-    pub fn stop_work(env: Env, stream_id: u64) -> Result<(), StreamError> {
-        let key = DataKey::Stream(stream_id);
-        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+    /// Helper to update accrued amount based on time passed
+    fn update_accrual(env: &Env, stream: &mut Stream) -> Result<(), StreamError> {
+        let current_time = env.ledger().timestamp();
 
-        stream.recipient.require_auth();
+        match stream.mode {
+            AccrualMode::Stopwatch => {
+                if stream.status != StreamStatus::Active {
+                    return Ok(());
+                }
 
-        if !stream.is_running {
-            return Err(StreamError::StreamNotRunning);
-        }
+                // A session never accrues past its length limit or heartbeat window
+                let accrue_until = current_time.min(Self::session_cutoff(stream));
 
-        Self::update_accrual(&env, &mut stream)?;
-        stream.is_running = false;
+                // Caps are tracked per UTC day, so a session is credited one day at a time
+                let mut accrued_until = accrue_until;
+                let mut from = stream.last_update_time;
+                while from < accrue_until && stream.total_accrued < stream.deposit {
+                    let day_end = (from / SECONDS_PER_DAY + 1) * SECONDS_PER_DAY;
+                    let to = accrue_until.min(day_end);
 
-        env.storage().persistent().set(&key, &stream);
+                    let (earned, seconds) = Self::accrue_period(stream, from, to)?;
+                    stream.total_accrued = stream.total_accrued.checked_add(earned).ok_or(StreamError::Overflow)?;
 
-        env.events().publish(
-            (Symbol::new(&env, "work_stopped"), stream.recipient, stream_id),
-            stream.total_accrued,
-        );
+                    // Running out of budget ends accrual after the last paid second, not at settle time
+                    if stream.total_accrued >= stream.deposit {
+                        accrued_until = from + seconds;
+                    }
+                    from = to;
+                }
+
+                if accrued_until > stream.last_update_time {
+                    stream.last_update_time = accrued_until;
+                }
+            }
+            AccrualMode::Linear => {
+                if stream.status == StreamStatus::Cancelled {
+                    return Ok(());
+                }
 
+                // Vesting is frozen while the stream is on hold
+                let vesting_time = stream.paused_at.unwrap_or(current_time);
+                stream.total_accrued = Self::linear_vested(stream, vesting_time)?;
+                stream.last_update_time = current_time;
+            }
+            AccrualMode::OneTime => {
+                let released = !stream.awaiting_approval
+                    && stream.paused_at.is_none()
+                    && current_time >= stream.start_time;
+                if released && stream.status != StreamStatus::Cancelled {
+                    stream.total_accrued = stream.deposit;
+                }
+            }
+            // Milestone and hybrid earnings only move on sender approval/review
+            AccrualMode::Milestone | AccrualMode::Hybrid => {}
+        }
         Ok(())
     }
 
-    /// Withdraws earned funds
-    pub fn withdraw(env: Env, stream_id: u64, amount: i128) -> Result<(), StreamError> {
-        let key = DataKey::Stream(stream_id);
-        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
-
-        stream.recipient.require_auth();
-
-        if stream.is_cancelled {
-            return Err(StreamError::StreamCancelled);
+    /// Credits a stopwatch stream for [from, to), which must lie within one UTC day,
+    /// splitting it across rate tiers and applying the daily/weekly caps and the deposit cap.
+    /// Returns the amount earned and the seconds it paid for
+    fn accrue_period(stream: &mut Stream, from: u64, to: u64) -> Result<(i128, u64), StreamError> {
+        let day = from / SECONDS_PER_DAY;
+        let week = (day + WEEK_START_OFFSET_DAYS) / 7;
+        if stream.day_usage.index != day {
+            stream.day_usage = PeriodUsage { index: day, seconds: 0, amount: 0 };
         }
-        if amount <= 0 {
-            return Err(StreamError::InvalidAmount);
+        if stream.week_usage.index != week {
+            stream.week_usage = PeriodUsage { index: week, seconds: 0, amount: 0 };
         }
 
-        // Update accrual to get latest earnings
-        if stream.is_running {
-            Self::update_accrual(&env, &mut stream)?;
+        let mut seconds = to - from;
+        if stream.caps.unit == CapUnit::Seconds {
+            let room = Self::cap_room(stream.caps.daily, stream.day_usage.seconds as i128)
+                .min(Self::cap_room(stream.caps.weekly, stream.week_usage.seconds as i128));
+            seconds = seconds.min(room.min(u64::MAX as i128) as u64);
         }
 
-        // Calculate available to withdraw
-        // Available = Total Accrued - Already Withdrawn
-        // Already Withdrawn = Deposit - Remaining Balance
-        // So: Available = Total Accrued - (Deposit - Remaining Balance)
-        //               = Total Accrued - Deposit + Remaining Balance
-        let withdrawn = stream.deposit.checked_sub(stream.remaining_balance).ok_or(StreamError::Overflow)?;
-        let available = stream.total_accrued.checked_sub(withdrawn).ok_or(StreamError::Overflow)?;
+        // Credited seconds past the weekly threshold are paid at the overtime rate
+        let regular_seconds = if stream.overtime.rate_per_second > 0 {
+            seconds.min(stream.overtime.threshold_seconds.saturating_sub(stream.week_usage.seconds))
+        } else {
+            seconds
+        };
+        let overtime_seconds = seconds - regular_seconds;
 
-        if amount > available {
-            return Err(StreamError::WithdrawExceedsAvailable);
+        let mut regular = stream.rate_per_second.checked_mul(regular_seconds as i128).ok_or(StreamError::Overflow)?;
+        let mut overtime = stream.overtime.rate_per_second.checked_mul(overtime_seconds as i128).ok_or(StreamError::Overflow)?;
+
+        // Token caps and the deposit cap trim the latest (overtime) earnings first
+        let mut room = stream.deposit - stream.total_accrued;
+        if stream.caps.unit == CapUnit::Tokens {
+            room = room
+                .min(Self::cap_room(stream.caps.daily, stream.day_usage.amount))
+                .min(Self::cap_room(stream.caps.weekly, stream.week_usage.amount));
         }
+        regular = regular.min(room);
+        overtime = overtime.min(room - regular);
+        let earned = regular + overtime;
 
-        let token = soroban_sdk::token::Client::new(&env, &stream.token_address);
-        token.transfer(&env.current_contract_address(), &stream.recipient, &amount);
+        // Only time that was actually paid counts toward the caps and the session log
+        let seconds = Self::paid_seconds(regular, stream.rate_per_second, regular_seconds)?
+            + Self::paid_seconds(overtime, stream.overtime.rate_per_second, overtime_seconds)?;
 
-        stream.remaining_balance = stream.remaining_balance.checked_sub(amount).ok_or(StreamError::Overflow)?;
-        
-        env.storage().persistent().set(&key, &stream);
+        stream.day_usage.seconds += seconds;
+        stream.day_usage.amount = stream.day_usage.amount.checked_add(earned).ok_or(StreamError::Overflow)?;
+        stream.week_usage.seconds += seconds;
+        stream.week_usage.amount = stream.week_usage.amount.checked_add(earned).ok_or(StreamError::Overflow)?;
+        stream.tier_totals.regular = stream.tier_totals.regular.checked_add(regular).ok_or(StreamError::Overflow)?;
+        stream.tier_totals.overtime = stream.tier_totals.overtime.checked_add(overtime).ok_or(StreamError::Overflow)?;
+        stream.session_seconds += seconds;
+        stream.session_amount = stream.session_amount.checked_add(earned).ok_or(StreamError::Overflow)?;
 
-        env.events().publish(
-            (Symbol::new(&env, "withdraw"), stream.recipient, stream_id),
-            amount,
-        );
+        Ok((earned, seconds))
+    }
 
-        Ok(())
+    /// Seconds of a tier covered by its (possibly trimmed) amount, rounding a part-paid second up
+    fn paid_seconds(amount: i128, rate_per_second: i128, seconds: u64) -> Result<u64, StreamError> {
+        if rate_per_second <= 0 {
+            return Ok(seconds);
+        }
+        let paid = amount.checked_add(rate_per_second - 1).ok_or(StreamError::Overflow)? / rate_per_second;
+        Ok(seconds.min(paid as u64))
     }
 
-    /// Admin cancels stream, refunds unearned budget
-    pub fn cancel_stream(env: Env, stream_id: u64) -> Result<(), StreamError> {
-        let key = DataKey::Stream(stream_id);
-        let mut stream: Stream = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+    /// What is left under a cap (0 = uncapped)
+    fn cap_room(cap: i128, used: i128) -> i128 {
+        if cap == 0 {
+            return i128::MAX;
+        }
+        (cap - used).max(0)
+    }
 
-        stream.sender.require_auth();
+    /// Brings accrual up to date and applies the time/budget driven transitions
+    fn settle(env: &Env, stream_id: u64, stream: &mut Stream) -> Result<(), StreamError> {
+        Self::update_accrual(env, stream)?;
 
-        if stream.is_cancelled {
-            return Err(StreamError::AlreadyCanceled);
+        let target = Self::settled_status(env, stream);
+        if stream.status == StreamStatus::Pending && target != StreamStatus::Pending {
+            Self::transition(env, stream_id, stream, StreamStatus::Active)?;
         }
+        if stream.status != StreamStatus::Completed && target == StreamStatus::Completed {
+            Self::transition(env, stream_id, stream, StreamStatus::Completed)?;
+        }
+        if stream.status == StreamStatus::Active && target == StreamStatus::Paused {
+            Self::transition(env, stream_id, stream, StreamStatus::Paused)?;
 
-        // Finalize accrual
-        if stream.is_running {
-            Self::update_accrual(&env, &mut stream)?;
-            stream.is_running = false;
+            env.events().publish(
+                (Symbol::new(env, "session_expired"), stream.recipient.clone(), stream_id),
+                (stream.last_update_time, stream.total_accrued),
+            );
         }
 
-        // Calculate refund
-        // Builder keeps: Total Accrued
-        // Admin gets: Remaining Balance - (Total Accrued - Already Withdrawn)
-        //           = Remaining Balance - Total Accrued + Already Withdrawn
-        //           = Remaining Balance - Total Accrued + (Deposit - Remaining Balance)
-        //           = Deposit - Total Accrued
-        // Wait, simpler:
-        // Contract holds: Remaining Balance
-        // Builder owns: (Total Accrued - Already Withdrawn)
-        // Admin owns: Remaining Balance - Builder's Share
-        
-        let withdrawn = stream.deposit.checked_sub(stream.remaining_balance).ok_or(StreamError::Overflow)?;
-        let builder_share_remaining = stream.total_accrued.checked_sub(withdrawn).ok_or(StreamError::Overflow)?;
-        let admin_refund = stream.remaining_balance.checked_sub(builder_share_remaining).ok_or(StreamError::Overflow)?;
+        // Sessions whose review window has run out are approved automatically
+        Self::approve_sessions_until(env, stream_id, stream, u32::MAX, true)?;
+        Ok(())
+    }
 
-        let token = soroban_sdk::token::Client::new(&env, &stream.token_address);
+    /// Latest time the current stopwatch session may accrue until (u64::MAX when unlimited)
+    fn session_cutoff(stream: &Stream) -> u64 {
+        let mut cutoff = u64::MAX;
+        if stream.max_session_seconds > 0 {
+            cutoff = cutoff.min(stream.session_start.saturating_add(stream.max_session_seconds));
+        }
+        if stream.heartbeat_grace > 0 {
+            cutoff = cutoff.min(stream.last_heartbeat.saturating_add(stream.heartbeat_grace));
+        }
+        cutoff
+    }
 
-        // Send builder their share
-        if builder_share_remaining > 0 {
-            token.transfer(&env.current_contract_address(), &stream.recipient, &builder_share_remaining);
+    /// Status a stream should be in once its accrual is up to date
+    fn settled_status(env: &Env, stream: &Stream) -> StreamStatus {
+        match stream.status {
+            StreamStatus::Pending | StreamStatus::Active | StreamStatus::Paused => {
+                if stream.total_accrued >= stream.deposit {
+                    StreamStatus::Completed
+                } else if stream.status == StreamStatus::Pending
+                    && stream.mode == AccrualMode::Linear
+                    && stream.paused_at.is_none()
+                    && env.ledger().timestamp() >= stream.start_time
+                {
+                    StreamStatus::Active
+                } else if stream.status == StreamStatus::Active
+                    && stream.mode == AccrualMode::Stopwatch
+                    && env.ledger().timestamp() >= Self::session_cutoff(stream)
+                {
+                    // Stale session: settles as if the builder had stopped at the cutoff
+                    StreamStatus::Paused
+                } else {
+                    stream.status
+                }
+            }
+            status => status,
         }
+    }
 
-        // Send admin refund
-        if admin_refund > 0 {
-            token.transfer(&env.current_contract_address(), &stream.sender, &admin_refund);
+    /// Single entry point for lifecycle changes; rejects illegal moves and emits the from/to states
+    fn transition(env: &Env, stream_id: u64, stream: &mut Stream, to: StreamStatus) -> Result<(), StreamError> {
+        let from = stream.status;
+
+        match (from, to) {
+            (StreamStatus::Pending, StreamStatus::Active)
+            | (StreamStatus::Active, StreamStatus::Paused)
+            | (StreamStatus::Paused, StreamStatus::Active)
+            | (StreamStatus::Active, StreamStatus::Completed)
+            | (StreamStatus::Paused, StreamStatus::Completed)
+            | (StreamStatus::Completed, StreamStatus::Paused)
+            | (StreamStatus::Pending, StreamStatus::Cancelled)
+            | (StreamStatus::Active, StreamStatus::Cancelled)
+            | (StreamStatus::Paused, StreamStatus::Cancelled)
+            | (StreamStatus::Completed, StreamStatus::Cancelled) => {}
+            (StreamStatus::Cancelled, StreamStatus::Cancelled) => return Err(StreamError::AlreadyCanceled),
+            (StreamStatus::Cancelled, _) => return Err(StreamError::StreamCancelled),
+            (StreamStatus::Active, StreamStatus::Active) => return Err(StreamError::StreamAlreadyRunning),
+            (StreamStatus::Completed, StreamStatus::Active) => return Err(StreamError::BudgetExceeded),
+            (_, StreamStatus::Paused) => return Err(StreamError::StreamNotRunning),
+            _ => return Err(StreamError::InvalidStateTransition),
         }
 
-        stream.is_cancelled = true;
-        stream.remaining_balance = 0;
-        
-        env.storage().persistent().set(&key, &stream);
+        // Every way out of a running stopwatch session (stop, expiry, hold, budget, cancel) logs it
+        if from == StreamStatus::Active && stream.mode == AccrualMode::Stopwatch {
+            Self::log_session(env, stream_id, stream)?;
+        }
+
+        stream.status = to;
 
         env.events().publish(
-            (Symbol::new(&env, "cancelled"), stream.sender, stream_id),
-            admin_refund,
+            (Symbol::new(env, "status_changed"), stream_id),
+            (from, to),
         );
 
         Ok(())
     }
 
-    /// Helper to update accrued amount based on time passed
-    fn update_accrual(env: &Env, stream: &mut Stream) -> Result<(), StreamError> {
-        let current_time = env.ledger().timestamp();
-        let elapsed = current_time.checked_sub(stream.last_update_time).unwrap_or(0);
-        
-        if elapsed > 0 {
-            let earned = stream.rate_per_second.checked_mul(elapsed as i128).ok_or(StreamError::Overflow)?;
-            stream.total_accrued = stream.total_accrued.checked_add(earned).ok_or(StreamError::Overflow)?;
-            
-            // Cap at deposit
-            if stream.total_accrued > stream.deposit {
-                stream.total_accrued = stream.deposit;
+    /// Approves pending sessions in order up to (not including) `up_to`; with `expired_only`
+    /// it stops at the first session still inside its review window. Returns the amount released.
+    fn approve_sessions_until(env: &Env, stream_id: u64, stream: &mut Stream, up_to: u32, expired_only: bool) -> Result<i128, StreamError> {
+        let now = env.ledger().timestamp();
+        let mut released: i128 = 0;
+
+        while stream.first_pending_session < up_to.min(stream.session_count) {
+            let key = DataKey::Session(stream_id, stream.first_pending_session);
+            let mut session: WorkSession = env.storage().persistent().get(&key).ok_or(StreamError::StreamNotFound)?;
+            if expired_only && now < session.stop.saturating_add(stream.review_window) {
+                break;
             }
-            
-            stream.last_update_time = current_time;
+
+            session.approved = true;
+            env.storage().persistent().set(&key, &session);
+
+            released = released.checked_add(session.amount).ok_or(StreamError::Overflow)?;
+            stream.first_pending_session += 1;
         }
+
+        stream.pending_amount = stream.pending_amount.checked_sub(released).ok_or(StreamError::Overflow)?;
+        Ok(released)
+    }
+
+    /// Accrued earnings that have passed review: excludes sessions awaiting approval and,
+    /// while a review window is set, the session that is still running
+    fn reviewed_accrued(stream: &Stream) -> Result<i128, StreamError> {
+        let mut unreviewed = stream.pending_amount;
+        if stream.review_window > 0 {
+            unreviewed = unreviewed.checked_add(stream.session_amount).ok_or(StreamError::Overflow)?;
+        }
+        stream.total_accrued.checked_sub(unreviewed).ok_or(StreamError::Overflow)
+    }
+
+    /// Stores the session that is closing and starts a fresh tally
+    fn log_session(env: &Env, stream_id: u64, stream: &mut Stream) -> Result<(), StreamError> {
+        let session = WorkSession {
+            start: stream.session_start,
+            stop: stream.last_update_time.max(stream.session_start),
+            seconds: stream.session_seconds,
+            amount: stream.session_amount,
+            proof_hash: BytesN::from_array(env, &[0; 32]),
+            approved: stream.review_window == 0,
+            rejected: false,
+        };
+        env.storage().persistent().set(&DataKey::Session(stream_id, stream.session_count), &session);
+
+        if session.approved {
+            stream.first_pending_session = stream.session_count + 1;
+        } else {
+            stream.pending_amount = stream.pending_amount.checked_add(session.amount).ok_or(StreamError::Overflow)?;
+        }
+        stream.session_count += 1;
+        stream.session_seconds = 0;
+        stream.session_amount = 0;
         Ok(())
     }
 
+    /// Amount of a linear stream's deposit vested at the given time,
+    /// excluding time already spent on hold (which pushed stop_time back)
+    fn linear_vested(stream: &Stream, time: u64) -> Result<i128, StreamError> {
+        if time <= stream.start_time {
+            return Ok(0);
+        }
+        if time >= stream.stop_time {
+            return Ok(stream.deposit);
+        }
+
+        let elapsed = (time - stream.start_time).saturating_sub(stream.paused_duration) as i128;
+        let duration = (stream.stop_time - stream.start_time - stream.paused_duration) as i128;
+        let vested = stream.deposit.checked_mul(elapsed).ok_or(StreamError::Overflow)?;
+
+        Ok(vested / duration)
+    }
+
     pub fn get_stream(env: Env, stream_id: u64) -> Result<Stream, StreamError> {
         env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)
     }
 
+    /// Computes the lifecycle status of a stream at the current ledger time
+    pub fn get_stream_status(env: Env, stream_id: u64) -> Result<StreamStatus, StreamError> {
+        let mut stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+        // Bring accrual up to date so a running clock that used up the budget reads as Completed
+        Self::update_accrual(&env, &mut stream)?;
+
+        Ok(Self::settled_status(&env, &stream))
+    }
+
+    /// Earnings split into regular and overtime, including a running session
+    pub fn get_tier_totals(env: Env, stream_id: u64) -> Result<TierTotals, StreamError> {
+        let mut stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+        Self::update_accrual(&env, &mut stream)?;
+
+        Ok(stream.tier_totals)
+    }
+
+    pub fn get_milestones(env: Env, stream_id: u64) -> Result<Vec<Milestone>, StreamError> {
+        env.storage().persistent().get(&DataKey::Milestones(stream_id)).ok_or(StreamError::StreamNotFound)
+    }
+
+    pub fn get_splits(env: Env, stream_id: u64) -> Result<Vec<Split>, StreamError> {
+        env.storage().persistent().get(&DataKey::Splits(stream_id)).ok_or(StreamError::StreamNotFound)
+    }
+
+    pub fn get_pool(env: Env, pool_id: u64) -> Result<Pool, StreamError> {
+        env.storage().persistent().get(&DataKey::Pool(pool_id)).ok_or(StreamError::PoolNotFound)
+    }
+
+    /// Pool members with their accrual brought up to the current ledger time
+    pub fn get_pool_members(env: Env, pool_id: u64) -> Result<Vec<PoolMember>, StreamError> {
+        let (mut pool, mut members) = Self::load_pool(&env, pool_id)?;
+
+        Self::accrue_pool(&env, &mut pool, &mut members)?;
+
+        Ok(members)
+    }
+
+    /// IDs of every stream the address receives from (including split shares)
+    pub fn get_recipient_streams(env: Env, recipient: Address) -> Vec<u64> {
+        env.storage().persistent().get(&DataKey::RecipientStreams(recipient)).unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_pending_transfer(env: Env, stream_id: u64) -> Result<RecipientTransfer, StreamError> {
+        env.storage().persistent().get(&DataKey::PendingTransfer(stream_id)).ok_or(StreamError::NoPendingTransfer)
+    }
+
+    /// Whether `operator` may currently start/stop work on the stream
+    pub fn is_stream_operator(env: Env, stream_id: u64, operator: Address) -> Result<bool, StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+        Ok(Self::is_operator(&env, stream_id, &stream, &operator))
+    }
+
+    /// Nonce the next signed clock or withdrawal payload for the stream must carry
+    pub fn get_clock_nonce(env: Env, stream_id: u64) -> u64 {
+        env.storage().persistent().get(&DataKey::ClockNonce(stream_id)).unwrap_or(0)
+    }
+
+    /// Logged sessions of a stream, oldest first, starting at index `start`
+    pub fn get_sessions(env: Env, stream_id: u64, start: u32, limit: u32) -> Result<Vec<WorkSession>, StreamError> {
+        let stream: Stream = env.storage().persistent().get(&DataKey::Stream(stream_id)).ok_or(StreamError::StreamNotFound)?;
+
+        let mut sessions = Vec::new(&env);
+        let end = start.saturating_add(limit).min(stream.session_count);
+        for index in start..end {
+            let session: WorkSession = env.storage().persistent().get(&DataKey::Session(stream_id, index)).ok_or(StreamError::StreamNotFound)?;
+            sessions.push_back(session);
+        }
+
+        Ok(sessions)
+    }
+
     pub fn get_next_stream_id(env: Env) -> u64 {
         env.storage().instance().get(&DataKey::NextStreamId).unwrap_or(0u64)
     }
@@ -1,7 +1,7 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, BytesN, Env};
 
 #[test]
 fn test_stream_flow() {
@@ -30,7 +30,7 @@ fn test_stream_flow() {
     let stop_time = start_time + 1000;
     let deposit = 1000;
 
-    let stream_id = client.create_stream(
+    let stream_id = client.create_linear_stream(
         &sender,
         &recipient,
         &deposit,
@@ -43,7 +43,7 @@ fn test_stream_flow() {
     let stream = client.get_stream(&stream_id);
     assert_eq!(stream.deposit, 1000);
     assert_eq!(stream.remaining_balance, 1000);
-    assert_eq!(stream.is_cancelled, false);
+    assert_eq!(stream.status, StreamStatus::Pending);
 
     // Advance time to halfway
     env.ledger().with_mut(|l| {
@@ -63,7 +63,7 @@ fn test_stream_flow() {
 
     // Verify stream is marked as cancelled
     let stream = client.get_stream(&stream_id);
-    assert_eq!(stream.is_cancelled, true);
+    assert_eq!(stream.status, StreamStatus::Cancelled);
     assert_eq!(stream.remaining_balance, 0);
 
     // Verify final balances (recipient should have 500, sender should have 500 returned)
@@ -91,7 +91,7 @@ fn test_stream_status() {
     let start_time = env.ledger().timestamp() + 100;
     let stop_time = start_time + 1000;
 
-    let stream_id = client.create_stream(
+    let stream_id = client.create_linear_stream(
         &sender,
         &recipient,
         &1000,
@@ -100,9 +100,9 @@ fn test_stream_status() {
         &stop_time,
     );
 
-    // Status should be UPCOMING
+    // Status should be PENDING before the start time
     let status = client.get_stream_status(&stream_id);
-    assert_eq!(status, StreamStatus::Upcoming);
+    assert_eq!(status, StreamStatus::Pending);
 
     // Advance to active period
     env.ledger().with_mut(|l| {
@@ -138,7 +138,7 @@ fn test_create_stream_zero_amount() {
     let stop_time = start_time + 1000;
 
     // Should return InvalidAmount error
-    let result = client.try_create_stream(
+    let result = client.try_create_linear_stream(
         &sender,
         &recipient,
         &0, // Invalid: zero amount
@@ -168,7 +168,7 @@ fn test_create_stream_same_sender_recipient() {
     let stop_time = start_time + 1000;
 
     // Should return SenderCannotBeRecipient error
-    let result = client.try_create_stream(
+    let result = client.try_create_linear_stream(
         &sender,
         &sender, // Invalid: same as sender
         &1000,
@@ -199,7 +199,7 @@ fn test_create_stream_invalid_time_range() {
     let stop_time = start_time; // Invalid: same as start
 
     // Should return InvalidTimeRange error
-    let result = client.try_create_stream(
+    let result = client.try_create_linear_stream(
         &sender,
         &recipient,
         &1000,
@@ -232,7 +232,7 @@ fn test_withdraw_before_start() {
     let start_time = env.ledger().timestamp() + 1000; // Future start
     let stop_time = start_time + 1000;
 
-    let stream_id = client.create_stream(
+    let stream_id = client.create_linear_stream(
         &sender,
         &recipient,
         &1000,
@@ -268,7 +268,7 @@ fn test_withdraw_exceeds_available() {
     let start_time = env.ledger().timestamp() + 100;
     let stop_time = start_time + 1000;
 
-    let stream_id = client.create_stream(
+    let stream_id = client.create_linear_stream(
         &sender,
         &recipient,
         &1000,
@@ -310,7 +310,7 @@ fn test_withdraw_nothing_available() {
     let start_time = env.ledger().timestamp() + 100;
     let stop_time = start_time + 1000;
 
-    let stream_id = client.create_stream(
+    let stream_id = client.create_linear_stream(
         &sender,
         &recipient,
         &1000,
@@ -354,7 +354,7 @@ fn test_cancel_twice() {
     let start_time = env.ledger().timestamp() + 100;
     let stop_time = start_time + 1000;
 
-    let stream_id = client.create_stream(
+    let stream_id = client.create_linear_stream(
         &sender,
         &recipient,
         &1000,
@@ -394,7 +394,7 @@ fn test_proper_authorization() {
     let stop_time = start_time + 1000;
 
     // Sender creates stream - should work
-    let stream_id = client.create_stream(
+    let stream_id = client.create_linear_stream(
         &sender,
         &recipient,
         &1000,
@@ -457,7 +457,7 @@ fn test_start_time_too_far_past() {
     let stop_time = current + 1000;
 
     // Should return InvalidStartTime error
-    let result = client.try_create_stream(
+    let result = client.try_create_linear_stream(
         &sender,
         &recipient,
         &1000,
@@ -490,7 +490,7 @@ fn test_withdraw_zero_amount() {
     let start_time = env.ledger().timestamp() + 100;
     let stop_time = start_time + 1000;
 
-    let stream_id = client.create_stream(
+    let stream_id = client.create_linear_stream(
         &sender,
         &recipient,
         &1000,
@@ -508,3 +508,2185 @@ fn test_withdraw_zero_amount() {
     
     assert_eq!(result, Err(Ok(StreamError::InvalidAmount)));
 }
+
+// 🧪 TEST: Stopwatch stream accrues only while the clock is running
+#[test]
+fn test_stopwatch_flow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    // 1000 budget at 2 per second
+    let stream_id = client.create_stream(&sender, &recipient, &1000, &token_contract, &2);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.mode, AccrualMode::Stopwatch);
+
+    // Idle time does not accrue
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    assert_eq!(client.try_withdraw(&stream_id, &1), Err(Ok(StreamError::NothingToWithdraw)));
+
+    // Work for 100 seconds
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    client.stop_work(&stream_id);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.total_accrued, 200);
+
+    // More idle time, still 200 available
+    env.ledger().with_mut(|l| {
+        l.timestamp += 1000;
+    });
+    client.withdraw(&stream_id, &200);
+    assert_eq!(token.balance(&recipient), 200);
+
+    // Cancel refunds the unearned budget
+    client.cancel_stream(&stream_id);
+    assert_eq!(token.balance(&sender), 800);
+}
+
+// 🧪 TEST: Linear streams cannot be clocked in/out
+#[test]
+fn test_linear_stream_rejects_start_work() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let stop_time = start_time + 1000;
+
+    let stream_id = client.create_linear_stream(
+        &sender,
+        &recipient,
+        &1000,
+        &token_contract,
+        &start_time,
+        &stop_time,
+    );
+
+    assert_eq!(client.try_start_work(&stream_id), Err(Ok(StreamError::UnsupportedAccrualMode)));
+    assert_eq!(client.try_stop_work(&stream_id), Err(Ok(StreamError::UnsupportedAccrualMode)));
+
+    // Fully vested after stop_time, capped at deposit
+    env.ledger().with_mut(|l| {
+        l.timestamp = stop_time + 500;
+    });
+    client.withdraw(&stream_id, &1000);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.remaining_balance, 0);
+}
+
+// 🧪 TEST: Stopwatch status follows the clock and the budget
+#[test]
+fn test_stopwatch_stream_status() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let stream_id = client.create_stream(&sender, &recipient, &1000, &token_contract, &10);
+    assert_eq!(client.get_stream_status(&stream_id), StreamStatus::Pending);
+
+    client.start_work(&stream_id);
+    assert_eq!(client.get_stream_status(&stream_id), StreamStatus::Active);
+
+    env.ledger().with_mut(|l| {
+        l.timestamp += 30;
+    });
+    client.stop_work(&stream_id);
+    assert_eq!(client.get_stream_status(&stream_id), StreamStatus::Paused);
+
+    // Clock left running past the budget reads as Completed without stop_work
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 500;
+    });
+    assert_eq!(client.get_stream_status(&stream_id), StreamStatus::Completed);
+}
+
+// 🧪 TEST: Lifecycle transitions reject illegal moves
+#[test]
+fn test_lifecycle_rejects_illegal_transitions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let stream_id = client.create_stream(&sender, &recipient, &1000, &token_contract, &10);
+
+    // Cannot stop a clock that never started
+    assert_eq!(client.try_stop_work(&stream_id), Err(Ok(StreamError::StreamNotRunning)));
+
+    client.start_work(&stream_id);
+    assert_eq!(client.try_start_work(&stream_id), Err(Ok(StreamError::StreamAlreadyRunning)));
+
+    // Run past the budget: stop settles straight into Completed
+    env.ledger().with_mut(|l| {
+        l.timestamp += 200;
+    });
+    client.stop_work(&stream_id);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.status, StreamStatus::Completed);
+    assert_eq!(stream.total_accrued, 1000);
+    assert_eq!(client.try_start_work(&stream_id), Err(Ok(StreamError::BudgetExceeded)));
+    assert_eq!(client.try_stop_work(&stream_id), Err(Ok(StreamError::StreamNotRunning)));
+
+    // Completed streams can still be closed out, after which nothing else is allowed
+    client.cancel_stream(&stream_id);
+    assert_eq!(client.try_start_work(&stream_id), Err(Ok(StreamError::StreamCancelled)));
+    assert_eq!(client.try_cancel_stream(&stream_id), Err(Ok(StreamError::AlreadyCanceled)));
+}
+
+// 🧪 TEST: Sender hold stops a running clock and blocks start_work until resumed
+#[test]
+fn test_pause_resume_stopwatch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let stream_id = client.create_stream(&sender, &recipient, &1000, &token_contract, &1);
+
+    assert_eq!(client.try_resume_stream(&stream_id), Err(Ok(StreamError::StreamNotOnHold)));
+
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+
+    // Pause force-stops the session and settles what was earned
+    client.pause_stream(&stream_id);
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.status, StreamStatus::Paused);
+    assert_eq!(stream.total_accrued, 100);
+    assert_eq!(stream.paused_at, Some(env.ledger().timestamp()));
+
+    assert_eq!(client.try_start_work(&stream_id), Err(Ok(StreamError::StreamOnHold)));
+    assert_eq!(client.try_pause_stream(&stream_id), Err(Ok(StreamError::StreamOnHold)));
+
+    env.ledger().with_mut(|l| {
+        l.timestamp += 50;
+    });
+    client.resume_stream(&stream_id);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.paused_at, None);
+    assert_eq!(stream.paused_duration, 50);
+    assert_eq!(stream.total_accrued, 100);
+
+    // Builder can clock in again
+    client.start_work(&stream_id);
+}
+
+// 🧪 TEST: Hold freezes linear vesting and shifts the end time
+#[test]
+fn test_pause_resume_linear() {
+    use soroban_sdk::testutils::Events;
+    use soroban_sdk::TryFromVal;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let stop_time = start_time + 1000;
+
+    let stream_id = client.create_linear_stream(
+        &sender,
+        &recipient,
+        &1000,
+        &token_contract,
+        &start_time,
+        &stop_time,
+    );
+
+    env.ledger().with_mut(|l| {
+        l.timestamp = start_time + 200;
+    });
+    client.pause_stream(&stream_id);
+    assert_eq!(client.get_stream_status(&stream_id), StreamStatus::Paused);
+
+    // Nothing vests while on hold
+    env.ledger().with_mut(|l| {
+        l.timestamp = start_time + 500;
+    });
+    assert_eq!(client.try_withdraw(&stream_id, &201), Err(Ok(StreamError::WithdrawExceedsAvailable)));
+    client.withdraw(&stream_id, &200);
+
+    client.resume_stream(&stream_id);
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.stop_time, stop_time + 300);
+    assert_eq!(stream.paused_duration, 300);
+    assert_eq!(stream.status, StreamStatus::Active);
+
+    // 100 seconds after resuming: 300 vested in total
+    env.ledger().with_mut(|l| {
+        l.timestamp = start_time + 600;
+    });
+    client.withdraw(&stream_id, &100);
+    assert_eq!(token.balance(&recipient), 300);
+
+    // Pausing again reports the hold time so far alongside the new pause
+    client.pause_stream(&stream_id);
+    let (_, _, data) = env.events().all().last().unwrap();
+    assert_eq!(<(u64, u64)>::try_from_val(&env, &data).unwrap(), (start_time + 600, 300));
+    client.resume_stream(&stream_id);
+
+    // Full budget only vests at the shifted end time
+    env.ledger().with_mut(|l| {
+        l.timestamp = stop_time + 299;
+    });
+    assert_eq!(client.get_stream_status(&stream_id), StreamStatus::Active);
+    env.ledger().with_mut(|l| {
+        l.timestamp = stop_time + 300;
+    });
+    assert_eq!(client.get_stream_status(&stream_id), StreamStatus::Completed);
+}
+
+// 🧪 TEST: Top-up raises the escrow and reopens an exhausted stopwatch stream
+#[test]
+fn test_top_up_reopens_completed_stream() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1500);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let stream_id = client.create_stream(&sender, &recipient, &1000, &token_contract, &10);
+
+    // Clock left running past the budget
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 150;
+    });
+
+    assert_eq!(client.try_top_up_stream(&stream_id, &0), Err(Ok(StreamError::InvalidAmount)));
+    client.top_up_stream(&stream_id, &500);
+
+    // Time past the old cap is not credited against the new funds
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.deposit, 1500);
+    assert_eq!(stream.remaining_balance, 1500);
+    assert_eq!(stream.total_accrued, 1000);
+    assert_eq!(stream.status, StreamStatus::Paused);
+    assert_eq!(token.balance(&sender), 0);
+    assert_eq!(token.balance(&contract_id), 1500);
+
+    // Builder keeps working on the same stream
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 20;
+    });
+    client.stop_work(&stream_id);
+    client.withdraw(&stream_id, &1200);
+    assert_eq!(token.balance(&recipient), 1200);
+
+    client.cancel_stream(&stream_id);
+    assert_eq!(token.balance(&sender), 300);
+
+    // Linear vesting would back-date part of a top-up, so it is rejected
+    let now = env.ledger().timestamp();
+    let linear_id = client.create_linear_stream(&sender, &recipient, &300, &token_contract, &now, &(now + 100));
+    assert_eq!(client.try_top_up_stream(&linear_id, &100), Err(Ok(StreamError::UnsupportedAccrualMode)));
+}
+
+// 🧪 TEST: Sender reclaims unearned budget, never below what was earned
+#[test]
+fn test_reduce_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let stream_id = client.create_stream(&sender, &recipient, &1000, &token_contract, &1);
+
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 300;
+    });
+
+    // 300 earned so far, only 700 can be reclaimed
+    assert_eq!(client.try_reduce_deposit(&stream_id, &701), Err(Ok(StreamError::RefundExceedsUnearned)));
+    client.reduce_deposit(&stream_id, &500);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.deposit, 500);
+    assert_eq!(stream.remaining_balance, 500);
+    assert_eq!(stream.total_accrued, 300);
+    assert_eq!(stream.status, StreamStatus::Active);
+    assert_eq!(token.balance(&sender), 500);
+
+    // The session keeps running against the smaller budget
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    client.reduce_deposit(&stream_id, &100);
+    assert_eq!(client.get_stream_status(&stream_id), StreamStatus::Completed);
+
+    client.withdraw(&stream_id, &400);
+    assert_eq!(token.balance(&recipient), 400);
+    assert_eq!(token.balance(&sender), 600);
+}
+
+// 🧪 TEST: Cliff locks withdrawals until both the time and amount are reached
+#[test]
+fn test_cliff_blocks_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1100);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let stop_time = start_time + 1000;
+    let options = StreamOptions {
+        cliff: Cliff {
+            time: start_time + 300,
+            amount: 400,
+            on_cancel: CliffCancelPolicy::Forfeit,
+        },
+        ..StreamOptions::default()
+    };
+
+    let stream_id = client.create_stream_with_options(
+        &sender,
+        &recipient,
+        &1000,
+        &token_contract,
+        &Schedule::Linear(start_time, stop_time),
+        &options,
+    );
+
+    // Time cliff passed, but only 300 accrued
+    env.ledger().with_mut(|l| {
+        l.timestamp = start_time + 300;
+    });
+    assert_eq!(client.try_withdraw(&stream_id, &100), Err(Ok(StreamError::CliffNotReached)));
+
+    // Both conditions met: everything accrued since day one is withdrawable
+    env.ledger().with_mut(|l| {
+        l.timestamp = start_time + 400;
+    });
+    client.withdraw(&stream_id, &400);
+    assert_eq!(token.balance(&recipient), 400);
+
+    // Using up the whole budget doesn't get around a time cliff
+    let stopwatch_options = StreamOptions {
+        cliff: Cliff { time: start_time + 2000, amount: 0, on_cancel: CliffCancelPolicy::Forfeit },
+        ..StreamOptions::default()
+    };
+    let stopwatch_id = client.create_stream_with_options(
+        &sender,
+        &recipient,
+        &100,
+        &token_contract,
+        &Schedule::Stopwatch(10),
+        &stopwatch_options,
+    );
+    client.start_work(&stopwatch_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 50;
+    });
+    assert_eq!(client.get_stream_status(&stopwatch_id), StreamStatus::Completed);
+    assert_eq!(client.try_withdraw(&stopwatch_id, &100), Err(Ok(StreamError::CliffNotReached)));
+}
+
+// 🧪 TEST: Cancelling before the cliff follows the per-stream policy
+#[test]
+fn test_cliff_cancel_policies() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let forfeit_recipient = Address::generate(&env);
+    let pro_rata_recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &2000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let cliff_time = env.ledger().timestamp() + 1000;
+    let forfeit_id = client.create_stream_with_options(
+        &sender,
+        &forfeit_recipient,
+        &1000,
+        &token_contract,
+        &Schedule::Stopwatch(1),
+        &StreamOptions {
+            cliff: Cliff { time: cliff_time, amount: 0, on_cancel: CliffCancelPolicy::Forfeit },
+            ..StreamOptions::default()
+        },
+    );
+    let pro_rata_id = client.create_stream_with_options(
+        &sender,
+        &pro_rata_recipient,
+        &1000,
+        &token_contract,
+        &Schedule::Stopwatch(1),
+        &StreamOptions {
+            cliff: Cliff { time: cliff_time, amount: 0, on_cancel: CliffCancelPolicy::ProRata },
+            ..StreamOptions::default()
+        },
+    );
+
+    client.start_work(&forfeit_id);
+    client.start_work(&pro_rata_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 200;
+    });
+
+    client.cancel_stream(&forfeit_id);
+    client.cancel_stream(&pro_rata_id);
+
+    assert_eq!(token.balance(&forfeit_recipient), 0);
+    assert_eq!(token.balance(&pro_rata_recipient), 200);
+    assert_eq!(token.balance(&sender), 1800);
+}
+
+// 🧪 TEST: A forgotten session stops accruing at the session limit
+#[test]
+fn test_max_session_auto_stop() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let options = StreamOptions {
+        max_session_seconds: 100,
+        ..StreamOptions::default()
+    };
+    let stream_id = client.create_stream_with_options(
+        &sender,
+        &recipient,
+        &1000,
+        &token_contract,
+        &Schedule::Stopwatch(1),
+        &options,
+    );
+
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 500;
+    });
+    assert_eq!(client.get_stream_status(&stream_id), StreamStatus::Paused);
+
+    // Touching the stream settles the stale session at the limit
+    assert_eq!(client.try_withdraw(&stream_id, &101), Err(Ok(StreamError::WithdrawExceedsAvailable)));
+    client.withdraw(&stream_id, &100);
+
+    // A new session can be started straight away
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 40;
+    });
+    client.stop_work(&stream_id);
+    assert_eq!(client.get_stream(&stream_id).total_accrued, 140);
+}
+
+// 🧪 TEST: Accrual stops a grace window after the last heartbeat
+#[test]
+fn test_heartbeat_grace() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let options = StreamOptions {
+        heartbeat_grace: 60,
+        ..StreamOptions::default()
+    };
+    let stream_id = client.create_stream_with_options(
+        &sender,
+        &recipient,
+        &1000,
+        &token_contract,
+        &Schedule::Stopwatch(1),
+        &options,
+    );
+
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 50;
+    });
+    client.heartbeat(&stream_id);
+
+    // Last heartbeat at +50, so accrual ends at +110
+    env.ledger().with_mut(|l| {
+        l.timestamp += 200;
+    });
+    assert_eq!(client.try_heartbeat(&stream_id), Err(Ok(StreamError::StreamNotRunning)));
+
+    client.stop_work(&stream_id);
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.total_accrued, 110);
+    assert_eq!(stream.status, StreamStatus::Paused);
+}
+
+// 🧪 TEST: Daily and weekly second caps, including a session across midnight
+#[test]
+fn test_daily_weekly_second_caps() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1_000_000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let hour = 3600;
+    let day = 24 * hour;
+    // Monday 2024-01-01 00:00 UTC
+    let monday = 1_704_067_200;
+
+    let options = StreamOptions {
+        caps: AccrualCaps { daily: 8 * hour as i128, weekly: 20 * hour as i128, unit: CapUnit::Seconds },
+        ..StreamOptions::default()
+    };
+    let stream_id = client.create_stream_with_options(
+        &sender,
+        &recipient,
+        &1_000_000,
+        &token_contract,
+        &Schedule::Stopwatch(1),
+        &options,
+    );
+
+    // Monday 20:00 -> Tuesday 10:00: 4h on Monday, 8h cap on Tuesday
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + 20 * hour;
+    });
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + day + 10 * hour;
+    });
+    client.stop_work(&stream_id);
+    assert_eq!(client.get_stream(&stream_id).total_accrued, 12 * hour as i128);
+
+    // Wednesday: 10h worked, only 8h count under the 20h weekly cap
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + 2 * day;
+    });
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + 2 * day + 10 * hour;
+    });
+    client.stop_work(&stream_id);
+    assert_eq!(client.get_stream(&stream_id).total_accrued, 20 * hour as i128);
+
+    // Thursday: weekly cap reached, nothing accrues
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + 3 * day;
+    });
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + 3 * day + hour;
+    });
+    client.stop_work(&stream_id);
+    assert_eq!(client.get_stream(&stream_id).total_accrued, 20 * hour as i128);
+
+    // Next Monday the week resets
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + 7 * day;
+    });
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + 7 * day + hour;
+    });
+    client.stop_work(&stream_id);
+    assert_eq!(client.get_stream(&stream_id).total_accrued, 21 * hour as i128);
+}
+
+// 🧪 TEST: Token-denominated daily cap
+#[test]
+fn test_daily_token_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1_000_000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let options = StreamOptions {
+        caps: AccrualCaps { daily: 500, weekly: 0, unit: CapUnit::Tokens },
+        ..StreamOptions::default()
+    };
+    let stream_id = client.create_stream_with_options(
+        &sender,
+        &recipient,
+        &1_000_000,
+        &token_contract,
+        &Schedule::Stopwatch(3),
+        &options,
+    );
+
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 1000;
+    });
+    client.stop_work(&stream_id);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.total_accrued, 500);
+    assert_eq!(stream.day_usage.amount, 500);
+    // Only the paid time is counted; the part-paid last second rounds up
+    assert_eq!(stream.day_usage.seconds, 167);
+}
+
+// 🧪 TEST: Time cut off by the budget doesn't use up a seconds cap
+#[test]
+fn test_second_cap_counts_paid_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let options = StreamOptions {
+        caps: AccrualCaps { daily: 200, weekly: 0, unit: CapUnit::Seconds },
+        ..StreamOptions::default()
+    };
+    let stream_id = client.create_stream_with_options(
+        &sender,
+        &recipient,
+        &100,
+        &token_contract,
+        &Schedule::Stopwatch(1),
+        &options,
+    );
+
+    // Clock left running past the budget: only 100s are paid
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 150;
+    });
+    client.top_up_stream(&stream_id, &500);
+    assert_eq!(client.get_stream(&stream_id).day_usage.seconds, 100);
+
+    // The rest of the day's cap is still available
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 150;
+    });
+    client.stop_work(&stream_id);
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.total_accrued, 200);
+    assert_eq!(stream.day_usage.seconds, 200);
+}
+
+// 🧪 TEST: Overtime rate beyond a weekly threshold, split inside one session
+#[test]
+fn test_overtime_tiers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1_000_000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let hour = 3600;
+    let day = 24 * hour;
+    // Monday 2024-01-01 00:00 UTC
+    let monday = 1_704_067_200;
+
+    // 2 per second up to 10h a week, 3 per second beyond
+    let options = StreamOptions {
+        overtime: OvertimeTier { threshold_seconds: 10 * hour, rate_per_second: 3 },
+        ..StreamOptions::default()
+    };
+    let stream_id = client.create_stream_with_options(
+        &sender,
+        &recipient,
+        &1_000_000,
+        &token_contract,
+        &Schedule::Stopwatch(2),
+        &options,
+    );
+
+    // Monday 8h
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + 8 * hour;
+    });
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + 16 * hour;
+    });
+    client.stop_work(&stream_id);
+
+    // Tuesday 4h: 2h regular, then 2h overtime in the same session
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + day;
+    });
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + day + 4 * hour;
+    });
+
+    // Running session is included in the query
+    let totals = client.get_tier_totals(&stream_id);
+    assert_eq!(totals.regular, 2 * 10 * hour as i128);
+    assert_eq!(totals.overtime, 3 * 2 * hour as i128);
+
+    client.stop_work(&stream_id);
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.total_accrued, totals.regular + totals.overtime);
+
+    // Next week starts at the base rate again
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + 7 * day;
+    });
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp = monday + 7 * day + hour;
+    });
+    let totals = client.get_tier_totals(&stream_id);
+    assert_eq!(totals.regular, 2 * 11 * hour as i128);
+    assert_eq!(totals.overtime, 3 * 2 * hour as i128);
+}
+
+// 🧪 TEST: Milestones are submitted in order and released on approval
+#[test]
+fn test_milestone_stream() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &600);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let milestones = soroban_sdk::vec![
+        &env,
+        MilestoneSpec { amount: 100, description_hash: BytesN::from_array(&env, &[1; 32]) },
+        MilestoneSpec { amount: 200, description_hash: BytesN::from_array(&env, &[2; 32]) },
+        MilestoneSpec { amount: 300, description_hash: BytesN::from_array(&env, &[3; 32]) },
+    ];
+    let stream_id = client.create_milestone_stream(&sender, &recipient, &token_contract, &milestones);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.mode, AccrualMode::Milestone);
+    assert_eq!(stream.deposit, 600);
+
+    let proof = BytesN::from_array(&env, &[9; 32]);
+
+    // Out of order and unsubmitted milestones are rejected
+    assert_eq!(client.try_submit_milestone(&stream_id, &1, &proof), Err(Ok(StreamError::InvalidMilestone)));
+    assert_eq!(client.try_approve_milestone(&stream_id, &0), Err(Ok(StreamError::MilestoneNotSubmitted)));
+
+    client.submit_milestone(&stream_id, &0, &proof);
+    assert_eq!(client.try_submit_milestone(&stream_id, &0, &proof), Err(Ok(StreamError::MilestoneAlreadySubmitted)));
+    assert_eq!(client.try_withdraw(&stream_id, &1), Err(Ok(StreamError::NothingToWithdraw)));
+
+    client.approve_milestone(&stream_id, &0);
+    client.withdraw(&stream_id, &100);
+    assert_eq!(token.balance(&recipient), 100);
+
+    // Rejected work can be resubmitted
+    client.submit_milestone(&stream_id, &1, &proof);
+    client.reject_milestone(&stream_id, &1);
+    assert_eq!(client.get_milestones(&stream_id).get(1).unwrap().status, MilestoneStatus::Rejected);
+    client.submit_milestone(&stream_id, &1, &proof);
+    client.approve_milestone(&stream_id, &1);
+
+    // Cancelling pays approved work and refunds the rest
+    client.submit_milestone(&stream_id, &2, &proof);
+    client.cancel_stream(&stream_id);
+    assert_eq!(token.balance(&recipient), 300);
+    assert_eq!(token.balance(&sender), 300);
+}
+
+// 🧪 TEST: Approving the last milestone completes the stream
+#[test]
+fn test_milestone_stream_completes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &500);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let milestones = soroban_sdk::vec![
+        &env,
+        MilestoneSpec { amount: 500, description_hash: BytesN::from_array(&env, &[1; 32]) },
+    ];
+    let stream_id = client.create_milestone_stream(&sender, &recipient, &token_contract, &milestones);
+    assert_eq!(client.try_start_work(&stream_id), Err(Ok(StreamError::UnsupportedAccrualMode)));
+    assert_eq!(client.try_top_up_stream(&stream_id, &100), Err(Ok(StreamError::UnsupportedAccrualMode)));
+
+    client.submit_milestone(&stream_id, &0, &BytesN::from_array(&env, &[7; 32]));
+    assert_eq!(client.get_stream_status(&stream_id), StreamStatus::Active);
+
+    client.approve_milestone(&stream_id, &0);
+    assert_eq!(client.get_stream_status(&stream_id), StreamStatus::Completed);
+    client.withdraw(&stream_id, &500);
+}
+
+// 🧪 TEST: HYBRID stream pays upfront, then the review-scaled remainder
+#[test]
+fn test_hybrid_stream() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    // 30% upfront
+    let stream_id = client.create_hybrid_stream(&sender, &recipient, &1000, &token_contract, &3000);
+
+    client.withdraw(&stream_id, &300);
+    assert_eq!(client.try_withdraw(&stream_id, &1), Err(Ok(StreamError::NothingToWithdraw)));
+
+    // Review at 0.8x: 560 of the 700 remainder to the builder, 140 back to the sender
+    assert_eq!(client.try_record_review(&stream_id, &10_001), Err(Ok(StreamError::InvalidAmount)));
+    client.record_review(&stream_id, &8000);
+    assert_eq!(client.try_record_review(&stream_id, &8000), Err(Ok(StreamError::ReviewAlreadyRecorded)));
+    assert_eq!(token.balance(&sender), 140);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.review_multiplier_bps, Some(8000));
+    assert_eq!(stream.total_accrued, 860);
+    assert_eq!(stream.status, StreamStatus::Completed);
+
+    client.withdraw(&stream_id, &560);
+    assert_eq!(token.balance(&recipient), 860);
+    assert_eq!(token.balance(&contract_id), 0);
+}
+
+// 🧪 TEST: Cancelling a HYBRID stream before review refunds the remainder
+#[test]
+fn test_hybrid_cancel_before_review() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let stream_id = client.create_hybrid_stream(&sender, &recipient, &1000, &token_contract, &3000);
+    client.cancel_stream(&stream_id);
+
+    assert_eq!(token.balance(&recipient), 300);
+    assert_eq!(token.balance(&sender), 700);
+}
+
+// 🧪 TEST: One-time payment released at its release time
+#[test]
+fn test_pay_once_release_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let release_time = env.ledger().timestamp() + 500;
+    let payment_id = client.pay_once(&sender, &recipient, &1000, &token_contract, &release_time, &false);
+
+    assert_eq!(client.get_stream(&payment_id).mode, AccrualMode::OneTime);
+    assert_eq!(client.try_withdraw(&payment_id, &1000), Err(Ok(StreamError::StreamNotStarted)));
+
+    env.ledger().with_mut(|l| {
+        l.timestamp = release_time;
+    });
+    assert_eq!(client.get_stream_status(&payment_id), StreamStatus::Completed);
+
+    // Claimed in parts, like any stream
+    client.withdraw(&payment_id, &400);
+    client.withdraw(&payment_id, &600);
+    assert_eq!(token.balance(&recipient), 1000);
+}
+
+// 🧪 TEST: One-time payment waiting for sender approval
+#[test]
+fn test_pay_once_requires_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &2000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let approved_id = client.pay_once(&sender, &recipient, &1000, &token_contract, &0, &true);
+    assert_eq!(client.try_withdraw(&approved_id, &1000), Err(Ok(StreamError::AwaitingApproval)));
+
+    client.approve_payment(&approved_id);
+    assert_eq!(client.try_approve_payment(&approved_id), Err(Ok(StreamError::ApprovalNotRequired)));
+    client.withdraw(&approved_id, &1000);
+
+    // Cancelled before approval: everything goes back to the sender
+    let cancelled_id = client.pay_once(&sender, &recipient, &1000, &token_contract, &0, &true);
+    client.cancel_stream(&cancelled_id);
+
+    assert_eq!(token.balance(&recipient), 1000);
+    assert_eq!(token.balance(&sender), 1000);
+}
+
+// 🧪 TEST: Split stream pays each recipient their own share
+#[test]
+fn test_split_stream() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let lead = Address::generate(&env);
+    let helper = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let mut recipients = Vec::new(&env);
+    recipients.push_back(SplitSpec { recipient: lead.clone(), share_bps: 7000 });
+    recipients.push_back(SplitSpec { recipient: helper.clone(), share_bps: 3000 });
+
+    let stream_id = client.create_split_stream(&sender, &recipients, &1000, &token_contract, &Schedule::Stopwatch(1));
+
+    // The first recipient runs the shared clock
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    client.stop_work(&stream_id);
+
+    // 100 earned: 70 / 30
+    assert_eq!(client.try_withdraw_share(&stream_id, &helper, &31), Err(Ok(StreamError::WithdrawExceedsAvailable)));
+    client.withdraw_share(&stream_id, &helper, &30);
+    client.withdraw(&stream_id, &70);
+    assert_eq!(client.try_withdraw_share(&stream_id, &sender, &1), Err(Ok(StreamError::UnauthorizedRecipient)));
+
+    assert_eq!(token.balance(&lead), 70);
+    assert_eq!(token.balance(&helper), 30);
+    assert_eq!(client.get_splits(&stream_id).get(1).unwrap().withdrawn, 30);
+
+    // Shares must add up to 100%
+    let mut bad = Vec::new(&env);
+    bad.push_back(SplitSpec { recipient: lead.clone(), share_bps: 5000 });
+    assert_eq!(
+        client.try_create_split_stream(&sender, &bad, &1000, &token_contract, &Schedule::Stopwatch(1)),
+        Err(Ok(StreamError::InvalidSplit))
+    );
+}
+
+// 🧪 TEST: Cancelling a split stream pays every recipient and refunds the rest
+#[test]
+fn test_split_stream_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let mut recipients = Vec::new(&env);
+    recipients.push_back(SplitSpec { recipient: a.clone(), share_bps: 3333 });
+    recipients.push_back(SplitSpec { recipient: b.clone(), share_bps: 3333 });
+    recipients.push_back(SplitSpec { recipient: c.clone(), share_bps: 3334 });
+
+    let stream_id = client.create_split_stream(&sender, &recipients, &1000, &token_contract, &Schedule::Stopwatch(1));
+
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    client.withdraw_share(&stream_id, &b, &20);
+    client.cancel_stream(&stream_id);
+
+    // 100 earned: 33 / 33 / 34 (rounding dust goes to the last share)
+    assert_eq!(token.balance(&a), 33);
+    assert_eq!(token.balance(&b), 33);
+    assert_eq!(token.balance(&c), 34);
+    assert_eq!(token.balance(&sender), 900);
+}
+
+// 🧪 TEST: Team pool members run their own clocks against one budget
+#[test]
+fn test_team_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let mut members = Vec::new(&env);
+    members.push_back(PoolMemberSpec { recipient: alice.clone(), rate_per_second: 2 });
+    members.push_back(PoolMemberSpec { recipient: bob.clone(), rate_per_second: 3 });
+
+    let pool_id = client.create_pool(&sender, &1000, &token_contract, &members);
+
+    // Alice works alone for 100s, then Bob joins
+    client.pool_start_work(&pool_id, &alice);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    client.pool_start_work(&pool_id, &bob);
+    assert_eq!(client.try_pool_start_work(&pool_id, &bob), Err(Ok(StreamError::StreamAlreadyRunning)));
+
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    client.pool_stop_work(&pool_id, &alice);
+
+    // Alice: 200s * 2 = 400, Bob: 100s * 3 = 300
+    client.pool_withdraw(&pool_id, &alice, &400);
+    assert_eq!(client.try_pool_withdraw(&pool_id, &bob, &301), Err(Ok(StreamError::WithdrawExceedsAvailable)));
+
+    // Bob alone uses up the remaining 300 after another 100s and his clock stops
+    env.ledger().with_mut(|l| {
+        l.timestamp += 500;
+    });
+    assert_eq!(client.try_pool_start_work(&pool_id, &alice), Err(Ok(StreamError::BudgetExceeded)));
+    assert!(!client.get_pool_members(&pool_id).get(1).unwrap().running);
+
+    client.pool_withdraw(&pool_id, &bob, &600);
+    assert_eq!(client.get_pool(&pool_id).status, StreamStatus::Completed);
+    assert_eq!(token.balance(&alice), 400);
+    assert_eq!(token.balance(&bob), 600);
+}
+
+// 🧪 TEST: Budget shared by rate when clocks overrun it, and cancelling settles everyone
+#[test]
+fn test_team_pool_exhaustion_and_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &2000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let mut members = Vec::new(&env);
+    members.push_back(PoolMemberSpec { recipient: alice.clone(), rate_per_second: 1 });
+    members.push_back(PoolMemberSpec { recipient: bob.clone(), rate_per_second: 3 });
+
+    // Both clocks run long enough to overrun the budget: 100 is split 1:3
+    let exhausted_id = client.create_pool(&sender, &100, &token_contract, &members);
+    client.pool_start_work(&exhausted_id, &alice);
+    client.pool_start_work(&exhausted_id, &bob);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 1000;
+    });
+    let settled = client.get_pool_members(&exhausted_id);
+    assert_eq!(settled.get(0).unwrap().accrued, 25);
+    assert_eq!(settled.get(1).unwrap().accrued, 75);
+
+    // Cancelling mid-session pays every member and refunds the rest
+    let cancelled_id = client.create_pool(&sender, &1000, &token_contract, &members);
+    client.pool_start_work(&cancelled_id, &alice);
+    client.pool_start_work(&cancelled_id, &bob);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 10;
+    });
+    client.cancel_pool(&cancelled_id);
+
+    assert_eq!(token.balance(&alice), 10);
+    assert_eq!(token.balance(&bob), 30);
+    assert_eq!(token.balance(&sender), 2000 - 100 - 40);
+    assert_eq!(client.try_cancel_pool(&cancelled_id), Err(Ok(StreamError::AlreadyCanceled)));
+}
+
+// 🧪 TEST: Payroll batch creates every stream from one transfer, or none at all
+#[test]
+fn test_create_streams_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &5000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let mut entries = Vec::new(&env);
+    entries.push_back(BatchStreamEntry { recipient: alice.clone(), deposit: 1000, rate_per_second: 1, options: StreamOptions::default() });
+    entries.push_back(BatchStreamEntry { recipient: bob.clone(), deposit: 2000, rate_per_second: 2, options: StreamOptions::default() });
+
+    let ids = client.create_streams_batch(&sender, &token_contract, &entries);
+    assert_eq!(ids.len(), 2);
+    assert_eq!(client.get_stream(&ids.get(1).unwrap()).recipient, bob);
+    assert_eq!(client.get_stream(&ids.get(1).unwrap()).rate_per_second, 2);
+    assert_eq!(token.balance(&contract_id), 3000);
+
+    // One bad entry rejects the whole manifest
+    entries.push_back(BatchStreamEntry { recipient: sender.clone(), deposit: 500, rate_per_second: 1, options: StreamOptions::default() });
+    assert_eq!(
+        client.try_create_streams_batch(&sender, &token_contract, &entries),
+        Err(Ok(StreamError::SenderCannotBeRecipient))
+    );
+    assert_eq!(client.get_next_stream_id(), 2);
+    assert_eq!(token.balance(&sender), 2000);
+}
+
+// 🧪 TEST: Batch withdraw sweeps a builder's streams with one transfer per token
+#[test]
+fn test_withdraw_many() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let other_token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let other_token = soroban_sdk::token::Client::new(&env, &other_token_contract);
+
+    let sender = Address::generate(&env);
+    let builder = Address::generate(&env);
+    let other_builder = Address::generate(&env);
+
+    soroban_sdk::token::StellarAssetClient::new(&env, &token_contract).mint(&sender, &5000);
+    soroban_sdk::token::StellarAssetClient::new(&env, &other_token_contract).mint(&sender, &5000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let start = env.ledger().timestamp();
+    let linear_id = client.create_linear_stream(&sender, &builder, &1000, &token_contract, &start, &(start + 1000));
+    let stopwatch_id = client.create_stream(&sender, &builder, &1000, &token_contract, &1);
+    let other_token_id = client.create_stream(&sender, &builder, &1000, &other_token_contract, &2);
+    let not_started_id = client.create_linear_stream(&sender, &builder, &1000, &token_contract, &(start + 5000), &(start + 6000));
+    client.create_stream(&sender, &other_builder, &1000, &token_contract, &1);
+
+    client.start_work(&stopwatch_id);
+    client.start_work(&other_token_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+
+    let receipts = client.withdraw_many(&builder, &None);
+    assert_eq!(receipts.len(), 4);
+    assert_eq!(receipts.get(0).unwrap(), WithdrawalReceipt { stream_id: linear_id, token_address: token_contract.clone(), amount: 100 });
+    assert_eq!(receipts.get(1).unwrap().amount, 100);
+    assert_eq!(receipts.get(2).unwrap().amount, 200);
+    assert_eq!(receipts.get(3).unwrap(), WithdrawalReceipt { stream_id: not_started_id, token_address: token_contract.clone(), amount: 0 });
+
+    assert_eq!(token.balance(&builder), 200);
+    assert_eq!(other_token.balance(&builder), 200);
+
+    // An explicit list can only name the caller's own streams
+    let mut ids = Vec::new(&env);
+    ids.push_back(stopwatch_id);
+    ids.push_back(4);
+    assert_eq!(client.try_withdraw_many(&builder, &Some(ids)), Err(Ok(StreamError::UnauthorizedRecipient)));
+
+    // Cancelled and fully paid streams drop out of the sweep
+    client.cancel_stream(&not_started_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp = start + 1000;
+    });
+    assert_eq!(client.withdraw_many(&builder, &None).len(), 3);
+    assert_eq!(client.get_recipient_streams(&builder).len(), 0);
+
+    // A top-up that reopens a stream brings it back
+    client.top_up_stream(&stopwatch_id, &100);
+    assert_eq!(client.get_recipient_streams(&builder), soroban_sdk::vec![&env, stopwatch_id]);
+}
+
+// 🧪 TEST: Withdraw everything available, to the recipient or another address
+#[test]
+fn test_withdraw_max_and_to() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let cold_wallet = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let stream_id = client.create_stream(&sender, &recipient, &1000, &token_contract, &1);
+    client.start_work(&stream_id);
+
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    assert_eq!(client.withdraw_max(&stream_id), 100);
+    assert_eq!(client.try_withdraw_max(&stream_id), Err(Ok(StreamError::NothingToWithdraw)));
+
+    env.ledger().with_mut(|l| {
+        l.timestamp += 50;
+    });
+    client.withdraw_to(&stream_id, &20, &cold_wallet);
+    assert_eq!(client.withdraw_max_to(&stream_id, &cold_wallet), 30);
+
+    assert_eq!(token.balance(&recipient), 100);
+    assert_eq!(token.balance(&cold_wallet), 50);
+    assert_eq!(client.get_stream(&stream_id).remaining_balance, 850);
+}
+
+// 🧪 TEST: Two-step recipient transfer, with earnings moving or settled first
+#[test]
+fn test_recipient_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let builder = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    let agency = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1600);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let stream_id = client.create_stream(&sender, &builder, &1000, &token_contract, &1);
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+
+    assert_eq!(client.try_accept_recipient_transfer(&stream_id), Err(Ok(StreamError::NoPendingTransfer)));
+
+    // Earnings go with the stream
+    client.propose_recipient_transfer(&stream_id, &new_wallet, &false);
+    client.accept_recipient_transfer(&stream_id);
+    assert_eq!(client.get_stream(&stream_id).recipient, new_wallet);
+    assert_eq!(client.try_get_pending_transfer(&stream_id), Err(Ok(StreamError::NoPendingTransfer)));
+    assert_eq!(client.get_recipient_streams(&builder).len(), 0);
+    assert_eq!(client.get_recipient_streams(&new_wallet).get(0).unwrap(), stream_id);
+
+    env.ledger().with_mut(|l| {
+        l.timestamp += 50;
+    });
+
+    // Earnings are settled to the current recipient before handing over
+    client.propose_recipient_transfer(&stream_id, &agency, &true);
+    client.accept_recipient_transfer(&stream_id);
+    assert_eq!(token.balance(&new_wallet), 150);
+
+    env.ledger().with_mut(|l| {
+        l.timestamp += 10;
+    });
+    client.stop_work(&stream_id);
+    client.withdraw(&stream_id, &10);
+    assert_eq!(token.balance(&agency), 10);
+    assert_eq!(token.balance(&builder), 0);
+
+    // Settling can't quietly pay 0 for earnings still behind the cliff
+    let cliff_id = client.create_stream_with_options(
+        &sender,
+        &builder,
+        &600,
+        &token_contract,
+        &Schedule::Stopwatch(1),
+        &StreamOptions {
+            cliff: Cliff { time: 0, amount: 500, on_cancel: CliffCancelPolicy::ProRata },
+            ..StreamOptions::default()
+        },
+    );
+    client.start_work(&cliff_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    client.propose_recipient_transfer(&cliff_id, &new_wallet, &true);
+    assert_eq!(client.try_accept_recipient_transfer(&cliff_id), Err(Ok(StreamError::CliffNotReached)));
+    assert_eq!(client.get_stream(&cliff_id).recipient, builder);
+}
+
+// 🧪 TEST: Admin reassigns a running stream to a new builder
+#[test]
+fn test_reassign_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let old_builder = Address::generate(&env);
+    let new_builder = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let stream_id = client.create_stream(&sender, &old_builder, &1000, &token_contract, &1);
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    client.withdraw(&stream_id, &30);
+
+    assert_eq!(client.try_reassign_recipient(&stream_id, &sender), Err(Ok(StreamError::SenderCannotBeRecipient)));
+    client.reassign_recipient(&stream_id, &new_builder);
+
+    // Old builder is paid out and the session is stopped
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(token.balance(&old_builder), 100);
+    assert_eq!(stream.recipient, new_builder);
+    assert_eq!(stream.status, StreamStatus::Paused);
+    assert_eq!(stream.session_start, 0);
+
+    // The new builder continues on the remaining budget
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 40;
+    });
+    client.stop_work(&stream_id);
+    client.withdraw(&stream_id, &40);
+    assert_eq!(token.balance(&new_builder), 40);
+    assert_eq!(client.get_stream(&stream_id).remaining_balance, 860);
+}
+
+// 🧪 TEST: Reassigning before the cliff settles the old builder by the cancel policy
+#[test]
+fn test_reassign_before_cliff() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let forfeit_builder = Address::generate(&env);
+    let pro_rata_builder = Address::generate(&env);
+    let new_builder = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &2000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let forfeit_id = client.create_stream_with_options(
+        &sender,
+        &forfeit_builder,
+        &1000,
+        &token_contract,
+        &Schedule::Stopwatch(1),
+        &StreamOptions {
+            cliff: Cliff { time: 0, amount: 300, on_cancel: CliffCancelPolicy::Forfeit },
+            ..StreamOptions::default()
+        },
+    );
+    let pro_rata_id = client.create_stream_with_options(
+        &sender,
+        &pro_rata_builder,
+        &1000,
+        &token_contract,
+        &Schedule::Stopwatch(1),
+        &StreamOptions {
+            cliff: Cliff { time: 0, amount: 300, on_cancel: CliffCancelPolicy::ProRata },
+            ..StreamOptions::default()
+        },
+    );
+
+    client.start_work(&forfeit_id);
+    client.start_work(&pro_rata_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 200;
+    });
+    client.reassign_recipient(&forfeit_id, &new_builder);
+    client.reassign_recipient(&pro_rata_id, &new_builder);
+
+    // Forfeited time goes back to the sender instead of moving to the new builder
+    let stream = client.get_stream(&forfeit_id);
+    assert_eq!(token.balance(&forfeit_builder), 0);
+    assert_eq!(token.balance(&sender), 200);
+    assert_eq!(stream.total_accrued, 0);
+    assert_eq!(stream.deposit, 800);
+
+    // Pro-rata pays the old builder in full despite the cliff
+    assert_eq!(token.balance(&pro_rata_builder), 200);
+
+    // The new builder starts from zero and has to reach the cliff themselves
+    client.start_work(&forfeit_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    client.stop_work(&forfeit_id);
+    assert_eq!(client.try_withdraw(&forfeit_id, &100), Err(Ok(StreamError::CliffNotReached)));
+}
+
+// 🧪 TEST: A forfeit reassignment during a hold keeps the rest of the linear schedule
+#[test]
+fn test_reassign_paused_linear_before_cliff() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let old_builder = Address::generate(&env);
+    let new_builder = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let start_time = env.ledger().timestamp();
+    let stop_time = start_time + 1000;
+    let options = StreamOptions {
+        cliff: Cliff { time: 0, amount: 250, on_cancel: CliffCancelPolicy::Forfeit },
+        ..StreamOptions::default()
+    };
+    let stream_id = client.create_stream_with_options(
+        &sender,
+        &old_builder,
+        &1000,
+        &token_contract,
+        &Schedule::Linear(start_time, stop_time),
+        &options,
+    );
+
+    // 200 vested before the hold, forfeited when the stream changes hands mid-hold
+    env.ledger().with_mut(|l| {
+        l.timestamp = start_time + 200;
+    });
+    client.pause_stream(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp = start_time + 700;
+    });
+    client.reassign_recipient(&stream_id, &new_builder);
+    assert_eq!(token.balance(&sender), 200);
+
+    // The remaining 800 still vest over the 800 seconds that were left
+    env.ledger().with_mut(|l| {
+        l.timestamp = start_time + 800;
+    });
+    client.resume_stream(&stream_id);
+    assert_eq!(client.get_stream(&stream_id).stop_time, stop_time + 600);
+
+    env.ledger().with_mut(|l| {
+        l.timestamp = start_time + 1100;
+    });
+    assert_eq!(client.withdraw_max(&stream_id), 300);
+
+    env.ledger().with_mut(|l| {
+        l.timestamp = stop_time + 600;
+    });
+    assert_eq!(client.withdraw_max(&stream_id), 500);
+    assert_eq!(token.balance(&new_builder), 800);
+    assert_eq!(token.balance(&old_builder), 0);
+}
+
+// 🧪 TEST: A reassigned builder starts with empty caps and overtime counters
+#[test]
+fn test_reassign_resets_usage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let old_builder = Address::generate(&env);
+    let new_builder = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1_000_000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let options = StreamOptions {
+        caps: AccrualCaps { daily: 4000, weekly: 0, unit: CapUnit::Seconds },
+        overtime: OvertimeTier { threshold_seconds: 3600, rate_per_second: 3 },
+        ..StreamOptions::default()
+    };
+    let stream_id = client.create_stream_with_options(
+        &sender,
+        &old_builder,
+        &1_000_000,
+        &token_contract,
+        &Schedule::Stopwatch(1),
+        &options,
+    );
+
+    // The old builder reaches the overtime threshold
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 3600;
+    });
+    client.reassign_recipient(&stream_id, &new_builder);
+
+    // The new builder's first hour is paid at the base rate and counts from zero
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 1000;
+    });
+    client.stop_work(&stream_id);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.day_usage.seconds, 1000);
+    assert_eq!(stream.week_usage.seconds, 1000);
+    let totals = client.get_tier_totals(&stream_id);
+    assert_eq!(totals.regular, 4600);
+    assert_eq!(totals.overtime, 0);
+}
+
+// 🧪 TEST: Operators run the clock on a recipient's behalf
+#[test]
+fn test_operators() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let builder = Address::generate(&env);
+    let tracker = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &2000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let first_id = client.create_stream(&sender, &builder, &1000, &token_contract, &1);
+    let second_id = client.create_stream(&sender, &builder, &1000, &token_contract, &1);
+
+    assert_eq!(client.try_start_work_as(&first_id, &tracker), Err(Ok(StreamError::UnauthorizedOperator)));
+
+    // Scoped to one stream
+    client.set_operator(&builder, &tracker, &Some(first_id));
+    client.start_work_as(&first_id, &tracker);
+    assert_eq!(env.auths()[0].0, tracker);
+    assert!(!client.is_stream_operator(&second_id, &tracker));
+    assert_eq!(client.try_set_operator(&stranger, &tracker, &Some(first_id)), Err(Ok(StreamError::UnauthorizedRecipient)));
+
+    // Across all of the builder's streams
+    client.set_operator(&builder, &tracker, &None);
+    client.start_work_as(&second_id, &tracker);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    client.heartbeat_as(&second_id, &tracker);
+    client.stop_work_as(&second_id, &tracker);
+    assert_eq!(client.get_stream(&second_id).total_accrued, 100);
+
+    client.revoke_operator(&builder, &tracker, &None);
+    client.revoke_operator(&builder, &tracker, &Some(first_id));
+    assert_eq!(client.try_stop_work_as(&first_id, &tracker), Err(Ok(StreamError::UnauthorizedOperator)));
+
+    // The builder keeps full control
+    client.stop_work(&first_id);
+}
+
+// 🧪 TEST: Relayed clock actions signed with the recipient's session key
+#[test]
+fn test_signed_clock_actions() {
+    use ed25519_dalek::{Signer, SigningKey};
+    use soroban_sdk::xdr::ToXdr;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let builder = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    env.ledger().with_mut(|l| {
+        l.timestamp = 10_000;
+    });
+    let stream_id = client.create_stream(&sender, &builder, &1000, &token_contract, &1);
+
+    let signing_key = SigningKey::from_bytes(&[7; 32]);
+    let sign = |payload: &ClockPayload| -> BytesN<64> {
+        let message = (contract_id.clone(), payload.clone()).to_xdr(&env);
+        let mut bytes = [0u8; 1024];
+        let len = message.len() as usize;
+        message.copy_into_slice(&mut bytes[..len]);
+        BytesN::from_array(&env, &signing_key.sign(&bytes[..len]).to_bytes())
+    };
+
+    let start = ClockPayload { stream_id, action: ClockAction::Start, timestamp: 10_000, nonce: 0, relayer: sender.clone(), max_fee: 0 };
+    assert_eq!(client.try_submit_clock_action(&start, &sign(&start), &0), Err(Ok(StreamError::NoSessionKey)));
+
+    client.set_session_key(&stream_id, &BytesN::from_array(&env, &signing_key.verifying_key().to_bytes()));
+    client.submit_clock_action(&start, &sign(&start), &0);
+    assert_eq!(client.get_stream(&stream_id).status, StreamStatus::Active);
+
+    // Replays and stale payloads are rejected
+    assert_eq!(client.try_submit_clock_action(&start, &sign(&start), &0), Err(Ok(StreamError::InvalidNonce)));
+    env.ledger().with_mut(|l| {
+        l.timestamp += 1000;
+    });
+    let stale = ClockPayload { stream_id, action: ClockAction::Stop, timestamp: 10_000, nonce: 1, relayer: sender.clone(), max_fee: 0 };
+    assert_eq!(client.try_submit_clock_action(&stale, &sign(&stale), &0), Err(Ok(StreamError::StaleClockPayload)));
+
+    let stop = ClockPayload { stream_id, action: ClockAction::Stop, timestamp: 11_000, nonce: 1, relayer: sender.clone(), max_fee: 0 };
+    client.submit_clock_action(&stop, &sign(&stop), &0);
+    assert_eq!(client.get_stream(&stream_id).total_accrued, 1000);
+    assert_eq!(client.get_clock_nonce(&stream_id), 2);
+}
+
+// 🧪 TEST: Relayers are paid a capped fee out of the recipient's earnings
+#[test]
+fn test_relayer_fees() {
+    use ed25519_dalek::{Signer, SigningKey};
+    use soroban_sdk::testutils::Events;
+    use soroban_sdk::xdr::ToXdr;
+    use soroban_sdk::IntoVal;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let builder = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let exchange = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    env.ledger().with_mut(|l| {
+        l.timestamp = 10_000;
+    });
+    let stream_id = client.create_stream(&sender, &builder, &1000, &token_contract, &1);
+
+    let signing_key = SigningKey::from_bytes(&[9; 32]);
+    client.set_session_key(&stream_id, &BytesN::from_array(&env, &signing_key.verifying_key().to_bytes()));
+    let sign = |message: soroban_sdk::Bytes| -> BytesN<64> {
+        let mut bytes = [0u8; 1024];
+        let len = message.len() as usize;
+        message.copy_into_slice(&mut bytes[..len]);
+        BytesN::from_array(&env, &signing_key.sign(&bytes[..len]).to_bytes())
+    };
+
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+
+    // Stopping pays the fee out of the session it closes, within the signed maximum
+    let stop = ClockPayload { stream_id, action: ClockAction::Stop, timestamp: 10_100, nonce: 0, relayer: relayer.clone(), max_fee: 5 };
+    let stop_signature = sign((contract_id.clone(), stop.clone()).to_xdr(&env));
+    assert_eq!(client.try_submit_clock_action(&stop, &stop_signature, &6), Err(Ok(StreamError::FeeExceedsMax)));
+    client.submit_clock_action(&stop, &stop_signature, &5);
+    assert_eq!(token.balance(&relayer), 5);
+
+    // The fee is reported as `relayer_paid` only, not as a withdrawal by the builder
+    let withdraw_topics: soroban_sdk::Vec<soroban_sdk::Val> = (Symbol::new(&env, "withdraw"), builder.clone(), stream_id).into_val(&env);
+    assert!(!env.events().all().iter().any(|(_, topics, _)| topics == withdraw_topics));
+
+    // Withdrawal and fee share the same available balance (95 left)
+    let withdrawal = WithdrawPayload {
+        stream_id,
+        amount: 90,
+        destination: exchange.clone(),
+        timestamp: 10_100,
+        nonce: 1,
+        relayer: relayer.clone(),
+        max_fee: 10,
+    };
+    let withdrawal_signature = sign((contract_id.clone(), withdrawal.clone()).to_xdr(&env));
+    assert_eq!(
+        client.try_submit_withdrawal(&withdrawal, &withdrawal_signature, &6),
+        Err(Ok(StreamError::WithdrawExceedsAvailable))
+    );
+    client.submit_withdrawal(&withdrawal, &withdrawal_signature, &5);
+
+    assert_eq!(token.balance(&exchange), 90);
+    assert_eq!(token.balance(&relayer), 10);
+    assert_eq!(client.get_stream(&stream_id).remaining_balance, 900);
+    assert_eq!(client.get_clock_nonce(&stream_id), 2);
+}
+
+// 🧪 TEST: Every session is logged with its credit and optional proof hash
+#[test]
+fn test_session_log() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let builder = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1100);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let options = StreamOptions { max_session_seconds: 50, ..StreamOptions::default() };
+    let stream_id = client.create_stream_with_options(&sender, &builder, &1000, &token_contract, &Schedule::Stopwatch(2), &options);
+
+    // Session 0: stopped with a proof hash
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1_000;
+    });
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1_030;
+    });
+    let proof = BytesN::from_array(&env, &[0xab; 32]);
+    client.stop_work_with_proof(&stream_id, &builder, &proof);
+
+    // Session 1: forgotten clock, closed at the session limit
+    env.ledger().with_mut(|l| {
+        l.timestamp = 2_000;
+    });
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp = 3_000;
+    });
+    client.start_work(&stream_id);
+
+    // Session 2: stopped without proof
+    env.ledger().with_mut(|l| {
+        l.timestamp = 3_010;
+    });
+    client.stop_work(&stream_id);
+
+    let sessions = client.get_sessions(&stream_id, &0, &10);
+    assert_eq!(sessions.len(), 3);
+    assert_eq!(
+        sessions.get(0).unwrap(),
+        WorkSession { start: 1_000, stop: 1_030, seconds: 30, amount: 60, proof_hash: proof, approved: true, rejected: false }
+    );
+    assert_eq!(sessions.get(1).unwrap().stop, 2_050);
+    assert_eq!(sessions.get(1).unwrap().amount, 100);
+    assert_eq!(sessions.get(2).unwrap().proof_hash, BytesN::from_array(&env, &[0; 32]));
+
+    // Paging
+    let page = client.get_sessions(&stream_id, &2, &5);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().seconds, 10);
+    assert_eq!(client.get_sessions(&stream_id, &3, &5).len(), 0);
+
+    // A session that runs past the budget logs only the paid time
+    let small_id = client.create_stream(&sender, &builder, &100, &token_contract, &2);
+    client.start_work(&small_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    assert_eq!(client.withdraw_max(&small_id), 100);
+    assert_eq!(client.get_stream(&small_id).status, StreamStatus::Completed);
+    let session = client.get_sessions(&small_id, &0, &1).get(0).unwrap();
+    assert_eq!(session.seconds, 50);
+    assert_eq!(session.amount, 100);
+    // Logged as stopping where the budget ran out, not when it was settled
+    assert_eq!(session.stop, session.start + 50);
+}
+
+// 🧪 TEST: Sessions wait for approval (or the review window) before becoming withdrawable
+#[test]
+fn test_timesheet_review_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let builder = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let options = StreamOptions { review_window: 1000, ..StreamOptions::default() };
+    let stream_id = client.create_stream_with_options(&sender, &builder, &1000, &token_contract, &Schedule::Stopwatch(1), &options);
+
+    // Session 0: 100s, approved by the sender
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    assert_eq!(client.try_withdraw(&stream_id, &1), Err(Ok(StreamError::NothingToWithdraw)));
+    client.stop_work(&stream_id);
+    assert_eq!(client.try_withdraw(&stream_id, &1), Err(Ok(StreamError::NothingToWithdraw)));
+
+    // Session 1: 50s, left to the review window
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 50;
+    });
+    client.stop_work(&stream_id);
+
+    client.approve_sessions(&stream_id, &1);
+    assert!(client.get_sessions(&stream_id, &0, &2).get(0).unwrap().approved);
+    assert!(!client.get_sessions(&stream_id, &0, &2).get(1).unwrap().approved);
+    assert_eq!(client.try_withdraw(&stream_id, &101), Err(Ok(StreamError::WithdrawExceedsAvailable)));
+    client.withdraw(&stream_id, &100);
+
+    env.ledger().with_mut(|l| {
+        l.timestamp += 1000;
+    });
+    client.withdraw(&stream_id, &50);
+    assert_eq!(token.balance(&builder), 150);
+    assert_eq!(client.try_approve_sessions(&stream_id, &3), Err(Ok(StreamError::InvalidSession)));
+
+    // Session 2: still under review when cancelled, and paid anyway
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 30;
+    });
+    client.stop_work(&stream_id);
+
+    // A settling transfer waits until pending time is reviewed rather than handing it over unpaid
+    client.propose_recipient_transfer(&stream_id, &new_wallet, &true);
+    assert_eq!(client.try_accept_recipient_transfer(&stream_id), Err(Ok(StreamError::AwaitingApproval)));
+
+    client.cancel_stream(&stream_id);
+
+    assert_eq!(token.balance(&builder), 180);
+    assert_eq!(token.balance(&sender), 820);
+}
+
+// 🧪 TEST: The sender can reject time under review and get it refunded
+#[test]
+fn test_reject_sessions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token_admin = Address::generate(&env);
+    let token_contract = env.register_stellar_asset_contract(token_admin.clone());
+    let token = soroban_sdk::token::Client::new(&env, &token_contract);
+    let token_admin_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_contract);
+
+    let sender = Address::generate(&env);
+    let builder = Address::generate(&env);
+
+    token_admin_client.mint(&sender, &1000);
+
+    let contract_id = env.register_contract(None, StreamContract);
+    let client = StreamContractClient::new(&env, &contract_id);
+
+    let options = StreamOptions { review_window: 1000, ..StreamOptions::default() };
+    let stream_id = client.create_stream_with_options(&sender, &builder, &1000, &token_contract, &Schedule::Stopwatch(1), &options);
+
+    // Session 0 (100s) is approved, session 1 (50s) is disputed
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 100;
+    });
+    client.stop_work(&stream_id);
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 50;
+    });
+    client.stop_work(&stream_id);
+
+    assert_eq!(client.try_reject_sessions(&stream_id, &3), Err(Ok(StreamError::InvalidSession)));
+    client.approve_sessions(&stream_id, &1);
+    client.reject_sessions(&stream_id, &2);
+
+    let sessions = client.get_sessions(&stream_id, &0, &2);
+    assert!(!sessions.get(0).unwrap().rejected);
+    assert!(sessions.get(1).unwrap().rejected);
+    assert_eq!(token.balance(&sender), 50);
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.deposit, 950);
+    assert_eq!(stream.total_accrued, 100);
+
+    client.withdraw(&stream_id, &100);
+    assert_eq!(client.try_withdraw(&stream_id, &1), Err(Ok(StreamError::NothingToWithdraw)));
+
+    // Once the window has run out the session is approved and can't be rejected
+    client.start_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 30;
+    });
+    client.stop_work(&stream_id);
+    env.ledger().with_mut(|l| {
+        l.timestamp += 1000;
+    });
+    client.reject_sessions(&stream_id, &3);
+    assert!(client.get_sessions(&stream_id, &2, &1).get(0).unwrap().approved);
+    assert_eq!(token.balance(&sender), 50);
+    client.withdraw(&stream_id, &30);
+    assert_eq!(token.balance(&builder), 130);
+}
@@ -80,8 +80,8 @@ export default function BuilderStreamPage() {
               remainingBalance: Number(data.remaining_balance) / 10000000,
               totalAccrued: Number(data.total_accrued) / 10000000,
               lastUpdateTime: Number(data.last_update_time),
-              isRunning: data.is_running,
-              isCancelled: data.is_cancelled || false,
+              isRunning: data.status === 1, // StreamStatus::Active
+              isCancelled: data.status === 4, // StreamStatus::Cancelled
               tokenAddress: data.token_address.toString(),
               withdrawn: (Number(data.deposit) - Number(data.remaining_balance)) / 10000000,
               
@@ -158,8 +158,8 @@ export default function BuilderStreamPage() {
               remainingBalance: Number(data.remaining_balance) / 10000000,
               totalAccrued: Number(data.total_accrued) / 10000000,
               lastUpdateTime: Number(data.last_update_time),
-              isRunning: data.is_running,
-              isCancelled: data.is_cancelled || false,
+              isRunning: data.status === 1, // StreamStatus::Active
+              isCancelled: data.status === 4, // StreamStatus::Cancelled
               tokenAddress: data.token_address.toString(),
               withdrawn: (Number(data.deposit) - Number(data.remaining_balance)) / 10000000,
               
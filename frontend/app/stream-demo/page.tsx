@@ -57,7 +57,7 @@ export default function Home() {
               id,
               remainingBalance: Number(data.remaining_balance) / 10000000,
               status,
-              isCancelled: data.is_cancelled,
+              isCancelled: data.status === 4, // StreamStatus::Cancelled
             });
 
             return {
@@ -69,8 +69,8 @@ export default function Home() {
               remainingBalance: Number(data.remaining_balance) / 10000000,
               totalAccrued: Number(data.total_accrued) / 10000000,
               lastUpdateTime: Number(data.last_update_time),
-              isRunning: data.is_running,
-              isCancelled: data.is_cancelled || false,
+              isRunning: data.status === 1, // StreamStatus::Active
+              isCancelled: data.status === 4, // StreamStatus::Cancelled
               tokenAddress: data.token_address.toString(),
               withdrawn: (Number(data.deposit) - Number(data.remaining_balance)) / 10000000,
               
@@ -147,8 +147,8 @@ export default function Home() {
               remainingBalance: Number(data.remaining_balance) / 10000000,
               totalAccrued: Number(data.total_accrued) / 10000000,
               lastUpdateTime: Number(data.last_update_time),
-              isRunning: data.is_running,
-              isCancelled: data.is_cancelled || false,
+              isRunning: data.status === 1, // StreamStatus::Active
+              isCancelled: data.status === 4, // StreamStatus::Cancelled
               tokenAddress: data.token_address.toString(),
               withdrawn: (Number(data.deposit) - Number(data.remaining_balance)) / 10000000,
               
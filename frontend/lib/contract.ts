@@ -157,11 +157,12 @@ export const getStreamStatus = async (streamId: string) => {
   const statusCode = StellarSdk.scValToNative(result);
 
   // Map Soroban enum to frontend string
-  const statusMap: Record<number, 'UPCOMING' | 'ACTIVE' | 'COMPLETED' | 'CANCELLED'> = {
+  const statusMap: Record<number, 'UPCOMING' | 'ACTIVE' | 'PAUSED' | 'COMPLETED' | 'CANCELLED'> = {
     0: 'UPCOMING',
     1: 'ACTIVE',
-    2: 'COMPLETED',
-    3: 'CANCELLED',
+    2: 'PAUSED',
+    3: 'COMPLETED',
+    4: 'CANCELLED',
   };
 
   const mappedStatus = statusMap[statusCode] || 'ACTIVE';